@@ -9,6 +9,8 @@ import {
 import BN from "bn.js";
 import {
   findBSolTokenAccountAuthority,
+  findPoolAuthority,
+  findPoolEntry,
   type SunriseStakeConfig,
 } from "./util.js";
 import { STAKE_POOL_PROGRAM_ID } from "./constants.js";
@@ -23,15 +25,22 @@ import {
 import { getStakePoolAccount } from "./decodeStakePool.js";
 import { ValidatorStakeInfoLayout } from "@solana/spl-stake-pool";
 
+/**
+ * Deposit into Blaze, or, if `hasPoolEntry` is set, into another registered SPL pool
+ * with the same account layout.
+ */
 export const blazeDeposit = async (
   config: SunriseStakeConfig,
   program: Program<SunriseStake>,
   blaze: BlazeState,
   depositor: PublicKey,
   depositorGsolTokenAccount: PublicKey,
-  lamports: BN
+  lamports: BN,
+  hasPoolEntry = false
 ): Promise<Transaction> => {
-  const bsolTokenAccountAuthority = findBSolTokenAccountAuthority(config)[0];
+  const bsolTokenAccountAuthority = hasPoolEntry
+    ? findPoolAuthority(config, blaze.pool)[0]
+    : findBSolTokenAccountAuthority(config)[0];
   const bsolAssociatedTokenAddress = utils.token.associatedAddress({
     mint: blaze.bsolMint,
     owner: bsolTokenAccountAuthority,
@@ -52,6 +61,8 @@ export const blazeDeposit = async (
     managerFeeAccount: blaze.feesDepot,
     stakePoolTokenMint: blaze.bsolMint,
     stakePoolProgram: STAKE_POOL_PROGRAM_ID,
+    poolEntry: hasPoolEntry ? findPoolEntry(config, blaze.pool)[0] : null,
+    poolRegistry: config.poolRegistry,
   };
 
   return program.methods
@@ -111,6 +122,7 @@ export const blazeDepositStake = async (
     sysvarClock: SYSVAR_CLOCK_PUBKEY,
     nativeStakeProgram: StakeProgram.programId,
     stakePoolProgram: STAKE_POOL_PROGRAM_ID,
+    poolRegistry: config.poolRegistry,
   };
 
   return program.methods.splDepositStake().accounts(accounts).transaction();
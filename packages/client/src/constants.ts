@@ -133,6 +133,25 @@ export const NETWORK_FEE = 5000;
 
 export const MINIMUM_EXTRACTABLE_YIELD = 100_000_000; // 0.1 SOL
 
+/** Flags for the operations that can be paused, matching state::operation in the program */
+export const PausableOperation = {
+  DEPOSIT: 1 << 0,
+  DEPOSIT_STAKE_ACCOUNT: 1 << 1,
+  LIQUID_UNSTAKE: 1 << 2,
+  ORDER_UNSTAKE: 1 << 3,
+  LOCK: 1 << 4,
+  SPL_DEPOSIT: 1 << 5,
+  SPL_WITHDRAW: 1 << 6,
+  EXTRACT: 1 << 7,
+  ALL: (1 << 8) - 1,
+} as const;
+
+// The program running a registered liquid staking pool, in the form Anchor expects for enum arguments
+export const PoolType = {
+  MARINADE: { marinade: {} },
+  SPL: { spl: {} },
+} as const;
+
 export const EMPTY_EPOCH_REPORT: EpochReportAccount = {
   epoch: new BN(0),
   tickets: new BN(0),
@@ -140,4 +159,8 @@ export const EMPTY_EPOCH_REPORT: EpochReportAccount = {
   extractableYield: new BN(0),
   extractedYield: new BN(0),
   currentGsolSupply: new BN(0),
+  treasuryTickets: new BN(0),
+  treasuryOrderedLamports: new BN(0),
+  depositEpoch: new BN(0),
+  depositedLamports: new BN(0),
 };
@@ -2,6 +2,7 @@ import { type SunriseStake } from "./types/sunrise_stake.js";
 import idl from "./types/sunrise_stake.json";
 import { type AnchorProvider, Program, utils } from "@coral-xyz/anchor";
 import {
+  type AccountMeta,
   type AddressLookupTableAccount,
   ComputeBudgetProgram,
   type ConfirmOptions,
@@ -23,10 +24,13 @@ import {
   findBSolTokenAccountAuthority,
   findGSolMintAuthority,
   findMSolTokenAccountAuthority,
+  findPoolAuthority,
+  findPoolRegistry,
   findSplRebalanceStakeAccount,
   logKeys,
   marinadeTargetReached,
   type Options,
+  type StateUpdates,
   proportionalBN,
   setUpAnchor,
   type SunriseStakeConfig,
@@ -65,6 +69,7 @@ import {
   type EnvironmentConfig,
   MARINADE_TICKET_RENT,
   NETWORK_FEE,
+  type PoolType,
   SOLBLAZE_ENABLED,
   STAKE_POOL_PROGRAM_ID,
 } from "./constants.js";
@@ -84,6 +89,7 @@ import {
 import { type BlazeState } from "./types/Solblaze.js";
 import { getStakePoolAccount, type StakePool } from "./decodeStakePool.js";
 import { type EpochReportAccount } from "./types/EpochReportAccount.js";
+import { type Reserves } from "./types/Reserves.js";
 import { LockClient, type LockAccountSummary } from "./lock.js";
 import BN from "bn.js";
 import { getPriorityFee } from "./helius.js";
@@ -96,6 +102,7 @@ export * from "./types/sunrise_stake.js";
 export * from "./types/Details.js";
 export * from "./types/TicketAccount.js";
 export * from "./types/EpochReportAccount.js";
+export * from "./types/Reserves.js";
 export * from "./types/Solblaze.js";
 
 // export all constants
@@ -106,6 +113,7 @@ export {
   findImpactNFTMintAuthority,
   ZERO_BALANCE,
   type SendTransactionFn,
+  type StateUpdates,
 } from "./util.js";
 
 export class SunriseStakeClient {
@@ -180,7 +188,13 @@ export class SunriseStakeClient {
       blazeMintedGsol: sunriseStakeState.blazeMintedGsol,
       impactNFTStateAddress: this.env.impactNFT.state,
       options: this.options,
+      poolRegistry: null,
     };
+    const [poolRegistry] = findPoolRegistry(this.config);
+    const poolRegistryInfo = await this.provider.connection.getAccountInfo(
+      poolRegistry
+    );
+    this.config.poolRegistry = poolRegistryInfo !== null ? poolRegistry : null;
 
     this.stakerGSolTokenAccount = PublicKey.findProgramAddressSync(
       [
@@ -677,6 +691,90 @@ export class SunriseStakeClient {
     return transaction;
   }
 
+  /**
+   * Deposit into whichever of Marinade and Solblaze is furthest below its target allocation,
+   * as chosen by the program.
+   * @param lamports
+   * @param minGsolOut The minimum gSOL to receive, after any pool fees
+   * @param recipient The recipient of the gSOL. If not provided, the current staker will be used.
+   */
+  public async depositRouted(
+    lamports: BN,
+    minGsolOut: BN,
+    recipient?: PublicKey
+  ): Promise<Transaction> {
+    if (
+      this.marinadeState == null ||
+      this.config == null ||
+      this.stakerGSolTokenAccount == null ||
+      this.blazeState == null ||
+      this.msolTokenAccount == null ||
+      this.liqPoolTokenAccount == null ||
+      this.bsolTokenAccount == null
+    )
+      throw new Error("init not called");
+
+    const recipientAuthority = recipient ?? this.staker;
+    const recipientGsolTokenAccountAddress =
+      recipient != null
+        ? utils.token.associatedAddress({
+            mint: this.config.gsolMint,
+            owner: recipientAuthority,
+          })
+        : this.stakerGSolTokenAccount;
+    const gsolTokenAccount = await this.provider.connection.getAccountInfo(
+      recipientGsolTokenAccountAddress
+    );
+
+    const transaction = new Transaction();
+
+    if (gsolTokenAccount == null) {
+      const createUserTokenAccount = this.createGSolTokenAccountIx(
+        recipientGsolTokenAccountAddress,
+        recipient
+      );
+      transaction.add(createUserTokenAccount);
+    }
+
+    type Accounts = Parameters<
+      ReturnType<typeof this.program.methods.depositRouted>["accounts"]
+    >[0];
+
+    const accounts: Accounts = {
+      state: this.env.state,
+      gsolMint: this.config.gsolMint,
+      depositor: this.staker,
+      mintGsolTo: recipientGsolTokenAccountAddress,
+      msolMint: this.marinadeState.mSolMint.address,
+      liqPoolMint: this.marinadeState.lpMint.address,
+      liqPoolSolLegPda: await this.marinadeState.solLeg(),
+      liqPoolMsolLeg: this.marinadeState.mSolLeg,
+      liqPoolMsolLegAuthority: await this.marinadeState.mSolLegAuthority(),
+      liqPoolMintAuthority: await this.marinadeState.lpMintAuthority(),
+      reservePda: await this.marinadeState.reserveAddress(),
+      mintMsolTo: this.msolTokenAccount,
+      mintLiqPoolTo: this.liqPoolTokenAccount,
+      msolMintAuthority: await this.marinadeState.mSolMintAuthority(),
+      bsolTokenAccount: this.bsolTokenAccount,
+      stakePool: this.blazeState.pool,
+      stakePoolWithdrawAuthority: this.blazeState.withdrawAuthority,
+      reserveStakeAccount: this.blazeState.reserveAccount,
+      managerFeeAccount: this.blazeState.feesDepot,
+      stakePoolTokenMint: this.blazeState.bsolMint,
+      stakePoolProgram: STAKE_POOL_PROGRAM_ID,
+      poolRegistry: this.config.poolRegistry,
+    };
+
+    const depositTx = await this.program.methods
+      .depositRouted(lamports, minGsolOut)
+      .accounts(accounts)
+      .transaction();
+
+    transaction.add(depositTx);
+
+    return transaction;
+  }
+
   /**
    * Deposit an existing SPL Stake account
    * @param stakeAccountAddress
@@ -841,7 +939,10 @@ export class SunriseStakeClient {
     return this.program.methods
       .recoverTickets()
       .accounts(accounts)
-      .remainingAccounts(previousEpochTicketAccountMetas)
+      .remainingAccounts([
+        ...previousEpochTicketAccountMetas,
+        ...(await this.getPoolEntryAccountMetas()),
+      ])
       .instruction();
   }
 
@@ -900,6 +1001,7 @@ export class SunriseStakeClient {
     await this.program.methods
       .updateEpochReport()
       .accounts(accounts)
+      .remainingAccounts(await this.getPoolEntryAccountMetas())
       .rpc()
       .then(confirm(this.provider.connection));
   }
@@ -1297,6 +1399,7 @@ export class SunriseStakeClient {
     return this.program.methods
       .initEpochReport(new BN(0))
       .accounts(accounts)
+      .remainingAccounts(await this.getPoolEntryAccountMetas())
       .rpc();
   }
 
@@ -1318,6 +1421,42 @@ export class SunriseStakeClient {
     return account;
   }
 
+  /**
+   * Get what backs gSOL: the value held in each pool, the in-flight delayed unstake lamports,
+   * the gSOL supply and the extractable yield, all in lamports.
+   * Read-only - the instruction is simulated rather than sent.
+   */
+  public async getReserves(): Promise<Reserves> {
+    if (
+      this.marinadeState == null ||
+      this.blazeState == null ||
+      this.msolTokenAccount == null ||
+      this.liqPoolTokenAccount == null ||
+      this.bsolTokenAccount == null
+    ) {
+      throw new Error("init not called");
+    }
+
+    // the PDAs, including the epoch report account, are auto-resolved by Anchor
+    return this.program.methods
+      .getReserves()
+      .accounts({
+        state: this.env.state,
+        marinadeState: this.marinadeState.marinadeStateAddress,
+        blazeState: this.blazeState.pool,
+        msolMint: this.marinadeState.mSolMintAddress,
+        bsolMint: this.blazeState.bsolMint,
+        liqPoolMint: this.marinadeState.lpMint.address,
+        liqPoolSolLegPda: await this.marinadeState.solLeg(),
+        liqPoolMsolLeg: this.marinadeState.mSolLeg,
+        liqPoolTokenAccount: this.liqPoolTokenAccount,
+        getMsolFrom: this.msolTokenAccount,
+        getBsolFrom: this.bsolTokenAccount,
+      })
+      .remainingAccounts(await this.getPoolEntryAccountMetas())
+      .view();
+  }
+
   /**
    * Create an instruction that extracts yield from the sunrise protocol and sends it to the designated
    * yield account.
@@ -1360,6 +1499,7 @@ export class SunriseStakeClient {
     return this.program.methods
       .extractToTreasury()
       .accounts(accounts)
+      .remainingAccounts(await this.getPoolEntryAccountMetas())
       .instruction();
   }
 
@@ -1714,6 +1854,7 @@ export class SunriseStakeClient {
       blazeMintedGsol: new BN(0),
       impactNFTStateAddress: this.env.impactNFT.state,
       options,
+      poolRegistry: null,
     };
     const marinadeConfig = new MarinadeConfig({
       connection: this.provider.connection,
@@ -1823,7 +1964,8 @@ export class SunriseStakeClient {
     treasury: PublicKey,
     gsolMint: Keypair,
     env: Omit<EnvironmentConfig, "state">,
-    options: Options = {}
+    options: Options = {},
+    stateUpdateDelay?: BN
   ): Promise<SunriseStakeClient> {
     const sunriseStakeState = Keypair.generate();
     const client = new SunriseStakeClient(
@@ -1842,7 +1984,10 @@ export class SunriseStakeClient {
     );
 
     await client.program.methods
-      .registerState(parameters)
+      .registerState({
+        ...parameters,
+        stateUpdateDelay: stateUpdateDelay ?? null,
+      })
       .accounts(accounts)
       .signers([gsolMint, sunriseStakeState])
       .rpc()
@@ -1855,31 +2000,17 @@ export class SunriseStakeClient {
     return client;
   }
 
-  /**
-   * Updates the configuration of the sunrise state instance. This can be used to set a new yield account,
-   * or a new update authority, or set a new liquidity pool proportion.
-   * @param newTreasury
-   * @param newUpdateAuthority
-   * @param newliqPoolProportion
-   * @param newliqPoolMinProportion
-   * @param newMarinadeMintedGsol
-   * @param newBlazeMintedGsol
-   */
-  public async update({
+  private async getStateInput({
     newTreasury,
-    newUpdateAuthority,
     newliqPoolProportion,
     newliqPoolMinProportion,
     newMarinadeMintedGsol,
     newBlazeMintedGsol,
-  }: {
-    newTreasury?: PublicKey;
-    newUpdateAuthority?: PublicKey;
-    newliqPoolProportion?: number;
-    newliqPoolMinProportion?: number;
-    newMarinadeMintedGsol?: BN;
-    newBlazeMintedGsol?: BN;
-  }): Promise<void> {
+    newMaxTotalGsolSupply,
+    newMaxDepositPerEpoch,
+    newMarinadeTargetBps,
+    newBlazeTargetBps,
+  }: StateUpdates): Promise<{ accounts: any; parameters: any }> {
     if (this.config == null) throw new Error("init not called");
 
     const { accounts, parameters } = await this.getRegisterStateAccounts(
@@ -1887,17 +2018,40 @@ export class SunriseStakeClient {
       this.config.gsolMint
     );
 
-    await this.program.methods
-      .updateState({
+    return {
+      accounts,
+      parameters: {
         ...parameters,
-        updateAuthority: newUpdateAuthority ?? this.config.updateAuthority,
+        updateAuthority: this.config.updateAuthority,
         liqPoolProportion:
           newliqPoolProportion ?? this.config.liqPoolProportion,
         liqPoolMinProportion:
           newliqPoolMinProportion ?? this.config.liqPoolMinProportion,
         marinadeMintedGsol: newMarinadeMintedGsol ?? null,
         blazeMintedGsol: newBlazeMintedGsol ?? null,
-      })
+        maxTotalGsolSupply: newMaxTotalGsolSupply ?? null,
+        maxDepositPerEpoch: newMaxDepositPerEpoch ?? null,
+        marinadeTargetBps: newMarinadeTargetBps ?? null,
+        blazeTargetBps: newBlazeTargetBps ?? null,
+      },
+    };
+  }
+
+  /**
+   * Updates the configuration of the sunrise state instance, and creates any missing token accounts.
+   * The treasury and liquidity pool proportions are timelocked, and can only be changed with
+   * queueStateUpdate and executeStateUpdate.
+   * To change the update authority, use proposeUpdateAuthority and acceptUpdateAuthority.
+   * @param newMarinadeMintedGsol
+   * @param newBlazeMintedGsol
+   * @param newMaxTotalGsolSupply
+   * @param newMaxDepositPerEpoch
+   */
+  public async update(updates: StateUpdates): Promise<void> {
+    const { accounts, parameters } = await this.getStateInput(updates);
+
+    await this.program.methods
+      .updateState(parameters)
       .accounts(accounts)
       .rpc()
       .then(confirm(this.provider.connection));
@@ -1905,6 +2059,287 @@ export class SunriseStakeClient {
     await this.init();
   }
 
+  /**
+   * Queue a change to the configuration of the sunrise state instance, such as a new treasury
+   * or liquidity pool proportion.
+   * The change can be executed with executeStateUpdate once the eta has passed.
+   * Only the timelocked fields can be queued; change the others with update.
+   * @param updates The changes to make
+   * @param eta The unix timestamp from which the change can be executed.
+   *   Must be at least the state update delay from now.
+   */
+  public async queueStateUpdate(
+    updates: StateUpdates,
+    eta: BN
+  ): Promise<void> {
+    const { parameters } = await this.getStateInput(updates);
+
+    // the pending state change PDA is auto-resolved by Anchor
+    await this.program.methods
+      .queueStateUpdate(parameters, eta)
+      .accounts({
+        state: this.env.state,
+        payer: this.provider.publicKey,
+        updateAuthority: this.config!.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * Apply the change queued with queueStateUpdate.
+   */
+  public async executeStateUpdate(): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .executeStateUpdate()
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+
+    await this.init();
+  }
+
+  /**
+   * Discard the change queued with queueStateUpdate.
+   */
+  public async cancelStateUpdate(): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .cancelStateUpdate()
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * Propose a new update authority for the sunrise state instance.
+   * The change takes effect once the new authority calls acceptUpdateAuthority.
+   * @param newUpdateAuthority
+   */
+  public async proposeUpdateAuthority(
+    newUpdateAuthority: PublicKey
+  ): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .proposeUpdateAuthority(newUpdateAuthority)
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+
+    await this.init();
+  }
+
+  /**
+   * Accept the update authority role proposed in proposeUpdateAuthority.
+   * @param newUpdateAuthority The proposed authority, which must sign the transaction
+   */
+  public async acceptUpdateAuthority(
+    newUpdateAuthority: Keypair
+  ): Promise<void> {
+    await this.program.methods
+      .acceptUpdateAuthority()
+      .accounts({
+        state: this.env.state,
+        newUpdateAuthority: newUpdateAuthority.publicKey,
+      })
+      .signers([newUpdateAuthority])
+      .rpc()
+      .then(confirm(this.provider.connection));
+
+    await this.init();
+  }
+
+  /**
+   * Set or clear the operator, a key that may run the SPL rebalancing instructions
+   * without being able to change the configuration.
+   * @param operator The new operator, or null to clear it
+   */
+  public async setOperator(operator: PublicKey | null): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .setOperator(operator)
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * Pause the given operations, in addition to any already paused.
+   * Must be signed by the pause guardian or the update authority.
+   * @param operations A combination of PausableOperation flags
+   */
+  public async pause(operations: number): Promise<Transaction> {
+    return this.program.methods
+      .pause(operations)
+      .accounts({
+        state: this.env.state,
+        pauseGuardian: this.staker,
+      })
+      .transaction();
+  }
+
+  /**
+   * Set the paused operations, unpausing any not included.
+   * @param pausedOperations A combination of PausableOperation flags
+   */
+  public async setPausedOperations(pausedOperations: number): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .setPausedOperations(pausedOperations)
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * Set or clear the pause guardian, a key that may pause operations but nothing else.
+   * @param pauseGuardian The new pause guardian, or null to clear it
+   */
+  public async setPauseGuardian(pauseGuardian: PublicKey | null): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .setPauseGuardian(pauseGuardian)
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * Add a liquid staking pool to the pool registry, creating the registry if needed.
+   * @param poolType One of the PoolType values
+   * @param pool The pool's state account
+   */
+  public async registerPool(
+    poolType: (typeof PoolType)[keyof typeof PoolType],
+    pool: PublicKey
+  ): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    // the pool registry PDA is auto-resolved by Anchor
+    await this.program.methods
+      .registerPool(poolType)
+      .accounts({
+        state: this.env.state,
+        payer: this.provider.publicKey,
+        updateAuthority: this.config.updateAuthority,
+        pool,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * Create the entry for a registered SPL pool other than Blaze,
+   * and the token account that holds this instance's tokens of the pool.
+   * @param pool The stake pool account
+   */
+  public async initPoolEntry(pool: PublicKey): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    const stakePoolInfo = await getStakePoolAccount(
+      this.provider.connection,
+      pool
+    );
+    const poolAuthority = findPoolAuthority(this.config, pool)[0];
+    const createPoolTokenAccount =
+      createAssociatedTokenAccountIdempotentInstruction(
+        this.provider.publicKey,
+        utils.token.associatedAddress({
+          mint: stakePoolInfo.poolMint,
+          owner: poolAuthority,
+        }),
+        poolAuthority,
+        stakePoolInfo.poolMint
+      );
+
+    // the pool entry PDA is auto-resolved by Anchor
+    await this.program.methods
+      .initPoolEntry()
+      .accounts({
+        state: this.env.state,
+        payer: this.provider.publicKey,
+        updateAuthority: this.config.updateAuthority,
+        stakePool: pool,
+      })
+      .postInstructions([createPoolTokenAccount])
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
+  /**
+   * The (pool entry, stake pool, pool token account) triples of every pool entry,
+   * which the instructions valuing the holdings expect at the end of their remaining accounts.
+   */
+  private async getPoolEntryAccountMetas(): Promise<AccountMeta[]> {
+    if (this.config == null) throw new Error("init not called");
+    const config = this.config;
+
+    const poolEntries = await this.program.account.poolEntry.all([
+      {
+        memcmp: {
+          offset: 8,
+          bytes: this.env.state.toBase58(),
+        },
+      },
+    ]);
+
+    return poolEntries.flatMap(({ account, publicKey }) => [
+      { pubkey: publicKey, isSigner: false, isWritable: false },
+      { pubkey: account.pool, isSigner: false, isWritable: false },
+      {
+        pubkey: utils.token.associatedAddress({
+          mint: account.poolMint,
+          owner: findPoolAuthority(config, account.pool)[0],
+        }),
+        isSigner: false,
+        isWritable: false,
+      },
+    ]);
+  }
+
+  /**
+   * Enable or disable a pool in the pool registry.
+   * @param pool The pool's state account
+   * @param enabled
+   */
+  public async setPoolEnabled(pool: PublicKey, enabled: boolean): Promise<void> {
+    if (this.config == null) throw new Error("init not called");
+
+    await this.program.methods
+      .setPoolEnabled(pool, enabled)
+      .accounts({
+        state: this.env.state,
+        updateAuthority: this.config.updateAuthority,
+      })
+      .rpc()
+      .then(confirm(this.provider.connection));
+  }
+
   /**
    * Get the user's current balance, and the current gsol supply
    */
@@ -2190,12 +2625,12 @@ export class SunriseStakeClient {
   }
 
   // //////////////////////////
-  // SPL Rebalance Admin Functions
+  // SPL Rebalance Operator Functions
   // //////////////////////////
 
   /**
    * Move SOL from SPL stake pool (liquid reserve) directly to Marinade liquidity pool.
-   * Admin-only instruction for rebalancing funds between pools.
+   * Operator-only instruction for rebalancing funds between pools.
    * @param lamports The amount of lamports to move
    */
   public async moveSplLiquidToMarinade(lamports: BN): Promise<Transaction> {
@@ -2216,10 +2651,11 @@ export class SunriseStakeClient {
       >["accounts"]
     >[0];
 
-    // Note: accounts with `relations` (updateAuthority, marinadeState) and PDAs
+    // Note: accounts with `relations` (marinadeState) and PDAs
     // (bsolAccountAuthority, msolTokenAccountAuthority) are auto-resolved by Anchor
     const accounts: Accounts = {
       state: this.env.state,
+      operator: this.staker,
       payer: this.staker,
       stakePool: this.blazeState.pool,
       stakePoolWithdrawAuthority: this.blazeState.withdrawAuthority,
@@ -2246,7 +2682,7 @@ export class SunriseStakeClient {
 
   /**
    * Create a stake account from SPL stake pool and deactivate it.
-   * Admin-only instruction for rebalancing funds between pools.
+   * Operator-only instruction for rebalancing funds between pools.
    * The stake account will be deactivated and can be deposited to Marinade liq pool
    * after it fully deactivates (next epoch boundary).
    * @param index The index of the stake account PDA
@@ -2275,10 +2711,11 @@ export class SunriseStakeClient {
       ReturnType<typeof this.program.methods.createSplStakeAccount>["accounts"]
     >[0];
 
-    // Note: accounts with `relations` (updateAuthority) and PDAs
-    // (newStakeAccount, bsolAccountAuthority, msolTokenAccountAuthority) are auto-resolved by Anchor
+    // Note: PDAs (newStakeAccount, bsolAccountAuthority, msolTokenAccountAuthority)
+    // are auto-resolved by Anchor
     const accounts: Accounts = {
       state: this.env.state,
+      operator: this.staker,
       payer: this.staker,
       stakePool: this.blazeState.pool,
       validatorStakeList: this.blazeState.validatorList,
@@ -2300,7 +2737,7 @@ export class SunriseStakeClient {
 
   /**
    * Deposit a deactivated stake account (from SPL rebalancing) into Marinade liquidity pool.
-   * Admin-only instruction. The stake account must be fully deactivated.
+   * Operator-only instruction. The stake account must be fully deactivated.
    * @param index The index of the stake account PDA
    */
   public async depositSplStakeToLiquid(index: bigint): Promise<Transaction> {
@@ -2318,10 +2755,11 @@ export class SunriseStakeClient {
       >["accounts"]
     >[0];
 
-    // Note: accounts with `relations` (updateAuthority, marinadeState) and PDAs
+    // Note: accounts with `relations` (marinadeState) and PDAs
     // (stakeAccount, msolTokenAccountAuthority) are auto-resolved by Anchor
     const accounts: Accounts = {
       state: this.env.state,
+      operator: this.staker,
       payer: this.staker,
       liqPoolMint: this.marinadeState.lpMint.address,
       liqPoolMintAuthority: await this.marinadeState.lpMintAuthority(),
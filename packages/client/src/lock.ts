@@ -460,6 +460,7 @@ export class LockClient {
     const globalYieldAccruedSinceLastUpdate =
       epochReportAccount.extractableYield
         .add(epochReportAccount.extractedYield)
+        .add(epochReportAccount.treasuryOrderedLamports)
         .sub(this.lockAccount.sunriseYieldAtStart);
 
     const yieldAccruedWithUnstakeFee = globalYieldAccruedSinceLastUpdate
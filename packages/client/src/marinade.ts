@@ -68,6 +68,7 @@ export const deposit = async (
     mintLiqPoolTo: liqPoolAssociatedTokenAccountAddress,
     mintGsolTo: recipientGsolTokenAccount,
     msolMintAuthority: await marinadeState.mSolMintAuthority(),
+    poolRegistry: config.poolRegistry,
   };
 
   return program.methods.deposit(lamports).accounts(accounts).transaction();
@@ -119,6 +120,7 @@ export const depositStakeAccount = async (
     mintGsolTo: stakerGsolTokenAccount,
     msolMintAuthority: await marinadeState.mSolMintAuthority(),
     stakeProgram: StakeProgram.programId,
+    poolRegistry: config.poolRegistry,
   };
   const validatorIndex = await getValidatorIndex(marinadeState, voterAddress);
   return program.methods
@@ -10,4 +10,10 @@ export interface EpochReportAccount {
   extractedYield: BN;
 
   currentGsolSupply: BN;
+
+  treasuryTickets: BN;
+  treasuryOrderedLamports: BN;
+
+  depositEpoch: BN;
+  depositedLamports: BN;
 }
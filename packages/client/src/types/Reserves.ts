@@ -0,0 +1,16 @@
+import type BN from "bn.js";
+
+export interface Reserves {
+  lpValue: BN;
+  msolValue: BN;
+  bsolValue: BN;
+  delayedUnstakeLamports: BN;
+  treasuryDelayedUnstakeLamports: BN;
+
+  gsolSupply: BN;
+  marinadeMintedGsol: BN;
+  blazeMintedGsol: BN;
+
+  extractableYield: BN;
+  entryPoolsValue: BN;
+}
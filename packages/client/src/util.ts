@@ -62,6 +62,9 @@ export const enum ProgramDerivedAddressSeed {
   IMPACT_NFT_MINT_AUTHORITY = "impact_nft_mint_authority",
   IMPACT_NFT_MINT_ACCOUNT = "impact_nft_mint_account",
   SPL_REBALANCE_STAKE_ACCOUNT = "spl_rebalance_stake",
+  POOL_REGISTRY = "pool_registry",
+  POOL_ENTRY = "pool_entry",
+  POOL_AUTHORITY = "pool_authority",
 }
 
 export interface SunriseStakeConfig {
@@ -79,6 +82,7 @@ export interface SunriseStakeConfig {
 
   options: Options;
   impactNFTStateAddress: PublicKey | undefined; // a state can exist without an impact nft state
+  poolRegistry: PublicKey | null; // deposits must pass the pool registry once it exists
 }
 
 // Return the type of an element in an array
@@ -197,6 +201,28 @@ export const findSplRebalanceStakeAccount = (
   );
 };
 
+export const findPoolRegistry = (
+  config: SunriseStakeConfig
+): [PublicKey, number] =>
+  findProgramDerivedAddress(config, ProgramDerivedAddressSeed.POOL_REGISTRY);
+
+export const findPoolEntry = (
+  config: SunriseStakeConfig,
+  pool: PublicKey
+): [PublicKey, number] =>
+  findProgramDerivedAddress(config, ProgramDerivedAddressSeed.POOL_ENTRY, [
+    pool.toBuffer(),
+  ]);
+
+// Holds this instance's tokens of an SPL pool other than Blaze
+export const findPoolAuthority = (
+  config: SunriseStakeConfig,
+  pool: PublicKey
+): [PublicKey, number] =>
+  findProgramDerivedAddress(config, ProgramDerivedAddressSeed.POOL_AUTHORITY, [
+    pool.toBuffer(),
+  ]);
+
 export const logKeys = (transaction: Transaction): void => {
   transaction.instructions.forEach((instruction, j) => {
     instruction.keys.forEach((key, i) => {
@@ -285,6 +311,25 @@ export interface Options {
   sendTransaction?: SendTransactionFn;
 }
 
+/** Changes to the configuration of a sunrise state instance. Unset fields are left unchanged. */
+export interface StateUpdates {
+  newTreasury?: PublicKey;
+  newliqPoolProportion?: number;
+  newliqPoolMinProportion?: number;
+  newMarinadeMintedGsol?: BN;
+  newBlazeMintedGsol?: BN;
+  /** The maximum gSOL supply deposits may mint up to, or 0 for no limit */
+  newMaxTotalGsolSupply?: BN;
+  /** The maximum lamports that may be deposited per epoch, or 0 for no limit */
+  newMaxDepositPerEpoch?: BN;
+  /**
+   * The target allocations used to route deposits, in basis points.
+   * Must be set together and add up to 10000.
+   */
+  newMarinadeTargetBps?: number;
+  newBlazeTargetBps?: number;
+}
+
 /**
  * Find all open delayed unstake tickets for the given epoch
  * @param connection
@@ -6,7 +6,7 @@ import BN from "bn.js";
 
 /**
  * Create a stake account from SPL stake pool and deactivate it.
- * This is an admin-only operation for rebalancing funds from SPL to Marinade liquidity pool.
+ * This is an operator-only operation (the operator or update authority) for rebalancing funds from SPL to Marinade liquidity pool.
  *
  * The stake account will be created and immediately deactivated. It will be fully deactivated
  * at the next epoch boundary, after which it can be deposited to the Marinade liquidity pool.
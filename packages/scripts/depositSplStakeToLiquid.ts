@@ -5,7 +5,7 @@ import { WalletAdapterNetwork } from "@solana/wallet-adapter-base";
 
 /**
  * Deposit a deactivated stake account (from SPL rebalancing) into Marinade liquidity pool.
- * This is an admin-only operation. The stake account must be fully deactivated (wait for next epoch).
+ * This is an operator-only operation (the operator or update authority). The stake account must be fully deactivated (wait for next epoch).
  *
  * Usage:
  *   yarn workspace @sunrisestake/scripts run ts-node depositSplStakeToLiquid.ts <index>
@@ -1,6 +1,5 @@
 import "./util";
 import { AnchorProvider, Program } from "@coral-xyz/anchor";
-import BN from "bn.js";
 import { SystemProgram } from "@solana/web3.js";
 import {
   IDL,
@@ -12,18 +11,16 @@ import { SUNRISE_STAKE_STATE } from "@sunrisestake/app/src/lib/constants";
 /**
  * USAGE (devnet)
  *
- * ANCHOR_PROVIDER_URL=https://api.devnet.solana.com REACT_APP_SOLANA_NETWORK=devnet yarn ts-node packages/scripts/resizeState.ts 140
+ * ANCHOR_PROVIDER_URL=https://api.devnet.solana.com REACT_APP_SOLANA_NETWORK=devnet yarn ts-node packages/scripts/migrateEpochReport.ts
  */
 
-const resizeAmount = parseInt(process.argv[2], 10);
-
-console.log("Resizing to", resizeAmount);
+console.log("Resizing the epoch report account to the current layout");
 
 (async () => {
   const provider = AnchorProvider.env();
   const program = new Program<SunriseStake>(IDL, PROGRAM_ID, provider);
   await program.methods
-    .resizeState(new BN(resizeAmount))
+    .migrateEpochReport()
     .accounts({
       state: SUNRISE_STAKE_STATE,
       payer: provider.publicKey,
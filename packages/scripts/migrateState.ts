@@ -0,0 +1,31 @@
+import "./util";
+import { AnchorProvider, Program } from "@coral-xyz/anchor";
+import { SystemProgram } from "@solana/web3.js";
+import {
+  IDL,
+  SunriseStake,
+} from "@sunrisestake/app/src/lib/client/types/sunrise_stake";
+import { PROGRAM_ID } from "@sunrisestake/app/src/lib/client/util";
+import { SUNRISE_STAKE_STATE } from "@sunrisestake/app/src/lib/constants";
+
+/**
+ * USAGE (devnet)
+ *
+ * ANCHOR_PROVIDER_URL=https://api.devnet.solana.com REACT_APP_SOLANA_NETWORK=devnet yarn ts-node packages/scripts/migrateState.ts
+ */
+
+console.log("Migrating state to the current version");
+
+(async () => {
+  const provider = AnchorProvider.env();
+  const program = new Program<SunriseStake>(IDL, PROGRAM_ID, provider);
+  await program.methods
+    .migrateState()
+    .accounts({
+      state: SUNRISE_STAKE_STATE,
+      payer: provider.publicKey,
+      updateAuthority: provider.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+})().catch(console.error);
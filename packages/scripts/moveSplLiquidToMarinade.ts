@@ -6,7 +6,7 @@ import BN from "bn.js";
 
 /**
  * Move SOL from SPL stake pool (liquid reserve) directly to Marinade liquidity pool.
- * This is an admin-only operation for rebalancing funds between pools.
+ * This is an operator-only operation (the operator or update authority) for rebalancing funds between pools.
  *
  * This operation withdraws SOL from the SPL stake pool's liquid reserve and immediately
  * adds it to the Marinade liquidity pool. It does not require waiting for stake deactivation.
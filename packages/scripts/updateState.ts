@@ -1,4 +1,7 @@
-import { SunriseStakeClient } from "../client/src/index.js";
+import {
+  SunriseStakeClient,
+  type StateUpdates,
+} from "../client/src/index.js";
 import "./util.js";
 import { AnchorProvider } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
@@ -12,13 +15,9 @@ import BN from "bn.js";
 // devnet new treasury (Yield Router): Csr9LRZ4K2kYWHeHrvTJZYqA55jNSDhxtszJT3yRicQS
 
 const parseArgs = () => {
-  const updates: {
-    newTreasury?: PublicKey;
-    newliqPoolProportion?: number;
-    newliqPoolMinProportion?: number;
-    newMarinadeMintedGsol?: BN;
-    newBlazeMintedGsol?: BN;
-  } = {};
+  const updates: StateUpdates = {};
+  let eta: BN | undefined;
+  let action: "execute" | "cancel" | undefined;
 
   for (let i = 2; i < process.argv.length; i++) {
     const arg = process.argv[i];
@@ -39,16 +38,29 @@ const parseArgs = () => {
     } else if (arg === "--blazeMintedGsol" && nextArg) {
       updates.newBlazeMintedGsol = new BN(nextArg);
       i++;
+    } else if (arg === "--maxTotalGsolSupply" && nextArg) {
+      updates.newMaxTotalGsolSupply = new BN(nextArg);
+      i++;
+    } else if (arg === "--maxDepositPerEpoch" && nextArg) {
+      updates.newMaxDepositPerEpoch = new BN(nextArg);
+      i++;
+    } else if (arg === "--queue" && nextArg) {
+      eta = new BN(nextArg);
+      i++;
+    } else if (arg === "--execute") {
+      action = "execute";
+    } else if (arg === "--cancel") {
+      action = "cancel";
     }
   }
 
-  return updates;
+  return { updates, eta, action };
 };
 
 (async () => {
-  const updates = parseArgs();
+  const { updates, eta, action } = parseArgs();
 
-  if (Object.keys(updates).length === 0) {
+  if (Object.keys(updates).length === 0 && action === undefined) {
     console.log("Usage: updateState.ts [options]");
     console.log("Options:");
     console.log("  --liqPoolProportion <0-100>       Target LP proportion");
@@ -56,11 +68,19 @@ const parseArgs = () => {
     console.log("  --treasury <pubkey>               Treasury address");
     console.log("  --marinadeMintedGsol <lamports>   Reset marinade minted gsol");
     console.log("  --blazeMintedGsol <lamports>      Reset blaze minted gsol");
+    console.log("  --maxTotalGsolSupply <lamports>   gSOL supply cap (0 for none)");
+    console.log("  --maxDepositPerEpoch <lamports>   Per-epoch deposit limit (0 for none)");
+    console.log(
+      "  --queue <unix timestamp>          Queue the update, to execute after the timestamp"
+    );
+    console.log("  --execute                         Execute the queued update");
+    console.log("  --cancel                          Cancel the queued update");
+    console.log(
+      "The treasury and LP proportions are timelocked, and must be changed with --queue"
+    );
     process.exit(1);
   }
 
-  console.log("Updating state with:", updates);
-
   const provider = AnchorProvider.env();
   const client = await SunriseStakeClient.get(
       provider,
@@ -68,6 +88,26 @@ const parseArgs = () => {
       {
         verbose: true,
       });
+
+  if (action === "execute") {
+    await client.executeStateUpdate();
+    console.log("Queued state update executed successfully");
+    return;
+  }
+  if (action === "cancel") {
+    await client.cancelStateUpdate();
+    console.log("Queued state update cancelled");
+    return;
+  }
+
+  if (eta !== undefined) {
+    console.log("Queueing state update with:", updates);
+    await client.queueStateUpdate(updates, eta);
+    console.log("State update queued, executable from", eta.toString());
+    return;
+  }
+
+  console.log("Updating state with:", updates);
   await client.update(updates);
 
   console.log("State updated successfully");
@@ -12,3 +12,5 @@ export const lockLamports = new BN(LAMPORTS_PER_SOL); // Lock 1 SOL
 export const blazeDepositLamports = new BN(100 * LAMPORTS_PER_SOL);
 export const blazeUnstakeLamports = new BN(60 * LAMPORTS_PER_SOL);
 export const marinadeStakeDeposit = new BN(100 * LAMPORTS_PER_SOL);
+
+export const STATE_UPDATE_DELAY = 2; // seconds
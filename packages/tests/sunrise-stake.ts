@@ -24,6 +24,7 @@ import {
   getDelegatedAmount,
   log,
   waitForNextEpoch,
+  waitForTimestamp,
   expectBSolTokenBalance,
   initializeStakeAccount,
   impactNFTLevels,
@@ -40,6 +41,7 @@ import {
   lockLamports,
   marinadeStakeDeposit,
   orderUnstakeLamports,
+  STATE_UPDATE_DELAY,
   unstakeLamportsExceedLPBalance,
   unstakeLamportsUnderLPBalance,
 } from "./constants.js";
@@ -72,27 +74,47 @@ describe("sunrise-stake", () => {
       initialClientEnvironment,
       {
         verbose: Boolean(process.env.VERBOSE),
-      }
+      },
+      // a short timelock on state updates, so that they can be tested
+      new BN(STATE_UPDATE_DELAY)
     );
 
     log(await client.details());
   });
 
-  it("can update the state", async () => {
+  it("cannot update timelocked fields of the state directly", async () => {
+    const shouldFail = client.update({
+      newTreasury: Keypair.generate().publicKey,
+    });
+
+    return expect(shouldFail).to.be.rejectedWith(
+      "StateUpdateRequiresTimelock"
+    );
+  });
+
+  it("can update the state after the timelock", async () => {
     treasury = Keypair.generate();
     updateAuthority = Keypair.generate();
 
-    await client.update({
-      newTreasury: treasury.publicKey,
-      newUpdateAuthority: updateAuthority.publicKey,
-    });
+    const slot = await client.provider.connection.getSlot();
+    const now = await client.provider.connection.getBlockTime(slot);
+    const eta = now! + STATE_UPDATE_DELAY + 1;
+    await client.queueStateUpdate(
+      { newTreasury: treasury.publicKey },
+      new BN(eta)
+    );
+
+    // the change cannot be executed before the eta
+    await expect(client.executeStateUpdate()).to.be.rejectedWith(
+      "StateUpdateNotReady"
+    );
+
+    await waitForTimestamp(client.provider.connection, eta);
+    await client.executeStateUpdate();
 
     expect(client.config?.treasury.toBase58()).to.equal(
       treasury.publicKey.toBase58()
     );
-    expect(client.config?.updateAuthority.toBase58()).to.equal(
-      updateAuthority.publicKey.toBase58()
-    );
     // unchanged properties
     expect(client.config?.liqPoolProportion).to.equal(DEFAULT_LP_PROPORTION);
     expect(client.config?.liqPoolMinProportion).to.equal(
@@ -100,26 +122,25 @@ describe("sunrise-stake", () => {
     );
   });
 
-  it("can resize the state", async () => {
+  it("can transfer the update authority in two steps", async () => {
+    const previousUpdateAuthority = client.config!.updateAuthority;
+    await client.proposeUpdateAuthority(updateAuthority.publicKey);
+
+    // the proposal alone does not change the update authority
+    expect(client.config?.updateAuthority.toBase58()).to.equal(
+      previousUpdateAuthority.toBase58()
+    );
+
+    await client.acceptUpdateAuthority(updateAuthority);
+
+    expect(client.config?.updateAuthority.toBase58()).to.equal(
+      updateAuthority.publicKey.toBase58()
+    );
+  });
+
+  it("can migrate the state", async () => {
     await client.program.methods
-      .resizeState(
-        new BN(
-          32 +
-            32 +
-            32 +
-            32 +
-            1 +
-            1 +
-            1 +
-            1 +
-            32 +
-            8 +
-            8 +
-            1 +
-            8 + // Base size
-            10 // extra space
-        )
-      )
+      .migrateState()
       .accountsStrict({
         state: client.env.state,
         payer: client.provider.publicKey,
@@ -903,6 +924,21 @@ describe("sunrise-stake", () => {
     expectAmount(new BN(gsolBalance.value.amount), expectedGSol, 1);
   });
 
+  it("burns the staker's gSOL when withdrawing sol from blaze", async () => {
+    const withdrawLamports = new BN(LAMPORTS_PER_SOL);
+    const gsolBalanceBefore =
+      await client.provider.connection.getTokenAccountBalance(
+        client.stakerGSolTokenAccount!
+      );
+
+    await client.withdrawFromBlaze(withdrawLamports);
+
+    await expectStakerGSolTokenBalance(
+      client,
+      new BN(gsolBalanceBefore.value.amount).sub(withdrawLamports)
+    );
+  });
+
   it.skip("can withdraw gSOL directly to a stake account", async () => {
     // Skip this test for now - it requires validator stake accounts to be created in the test environment
     // The SPL stake pool's withdraw_stake instruction only works with validator stake accounts, not reserve accounts
@@ -3,6 +3,7 @@ import {
   getStakePoolAccount,
 } from "@sunrisestake/client";
 import {
+  type Connection,
   Keypair,
   type PublicKey,
   StakeProgram,
@@ -215,6 +216,22 @@ export const waitForNextEpoch = async (client: SunriseStakeClient) => {
   await client.provider.connection.removeSlotChangeListener(subscriptionId);
 };
 
+/**
+ * Wait until the cluster's clock has reached the given unix timestamp
+ */
+export const waitForTimestamp = async (
+  connection: Connection,
+  timestamp: number
+) => {
+  for (;;) {
+    const slot = await connection.getSlot();
+    const blockTime = await connection.getBlockTime(slot);
+    if (blockTime !== null && blockTime >= timestamp) return;
+    log("Waiting for timestamp", timestamp, "blockTime", blockTime);
+    await new Promise((resolve) => setTimeout(resolve, 500));
+  }
+};
+
 export const getBsolPrice = async (
   client: SunriseStakeClient
 ): Promise<number> => {
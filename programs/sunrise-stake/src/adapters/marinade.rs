@@ -0,0 +1,59 @@
+use super::StakePoolAdapter;
+use crate::{
+    marinade::accounts::MarinadeState,
+    utils::marinade::{self, GenericUnstakeProperties, OrderUnstakeProperties},
+    Deposit, ErrorCode,
+};
+use anchor_lang::prelude::*;
+
+/// The Marinade pool, valued in mSOL.
+/// Liquidity pool tokens are not pool tokens in this sense, and are valued separately.
+pub struct MarinadeAdapter {
+    pub marinade_state: MarinadeState,
+}
+
+impl MarinadeAdapter {
+    pub fn load(marinade_state: &UncheckedAccount) -> Result<Self> {
+        Ok(Self {
+            marinade_state: marinade::deserialize_marinade_state(marinade_state)?,
+        })
+    }
+}
+
+impl<'info> StakePoolAdapter<'info> for MarinadeAdapter {
+    type DepositSolAccounts = Deposit<'info>;
+    type WithdrawSolAccounts = GenericUnstakeProperties<'info>;
+    /// Withdrawing stake accounts from Marinade is not supported
+    type WithdrawStakeAccounts = ();
+    type DelayedUnstakeAccounts = OrderUnstakeProperties<'info>;
+
+    fn value_of_holdings(&self, pool_tokens: u64) -> Result<u64> {
+        marinade::calc_lamports_from_msol_amount(&self.marinade_state, pool_tokens)
+    }
+
+    fn deposit_sol(&self, accounts: &Self::DepositSolAccounts, lamports: u64) -> Result<()> {
+        marinade::deposit(accounts, lamports)
+    }
+
+    fn withdraw_sol(&self, accounts: &Self::WithdrawSolAccounts, lamports: u64) -> Result<()> {
+        let msol_lamports = marinade::calc_msol_from_lamports(&self.marinade_state, lamports)?;
+        marinade::unstake(accounts, msol_lamports)
+    }
+
+    fn withdraw_stake(
+        &self,
+        _accounts: &Self::WithdrawStakeAccounts,
+        _lamports: u64,
+    ) -> Result<()> {
+        err!(ErrorCode::UnsupportedPoolOperation)
+    }
+
+    fn delayed_unstake(
+        &self,
+        accounts: &Self::DelayedUnstakeAccounts,
+        lamports: u64,
+    ) -> Result<()> {
+        let msol_lamports = marinade::calc_msol_from_lamports(&self.marinade_state, lamports)?;
+        marinade::order_unstake(accounts, msol_lamports)
+    }
+}
@@ -0,0 +1,36 @@
+//! Adapters giving a common interface to the liquid staking pools that sunrise holds funds in.
+mod marinade;
+mod spl;
+
+pub use marinade::*;
+pub use spl::*;
+
+use anchor_lang::prelude::*;
+
+/// A liquid staking pool that sunrise can deposit into and withdraw from.
+///
+/// Each pool needs different accounts for each operation, so these are associated types.
+/// All amounts are in lamports; the adapter converts them to the pool's own token.
+/// None of the operations touch gSOL accounting, which is left to the caller.
+pub trait StakePoolAdapter<'info> {
+    type DepositSolAccounts;
+    type WithdrawSolAccounts;
+    type WithdrawStakeAccounts;
+    type DelayedUnstakeAccounts;
+
+    /// The SOL value of `pool_tokens` of the pool's liquid staking token
+    fn value_of_holdings(&self, pool_tokens: u64) -> Result<u64>;
+
+    /// Deposit `lamports` into the pool in exchange for pool tokens
+    fn deposit_sol(&self, accounts: &Self::DepositSolAccounts, lamports: u64) -> Result<()>;
+
+    /// Immediately withdraw `lamports` of SOL from the pool
+    fn withdraw_sol(&self, accounts: &Self::WithdrawSolAccounts, lamports: u64) -> Result<()>;
+
+    /// Withdraw `lamports` from the pool as an active stake account
+    fn withdraw_stake(&self, accounts: &Self::WithdrawStakeAccounts, lamports: u64) -> Result<()>;
+
+    /// Withdraw `lamports` from the pool, claimable once the stake has been deactivated
+    fn delayed_unstake(&self, accounts: &Self::DelayedUnstakeAccounts, lamports: u64)
+        -> Result<()>;
+}
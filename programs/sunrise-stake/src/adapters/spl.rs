@@ -0,0 +1,77 @@
+use super::StakePoolAdapter;
+use crate::{
+    sunrise_spl::{
+        deposit_sol_to_pool, withdraw_sol_from_pool, withdraw_stake_from_pool,
+        SplDepositSolProperties, SplWithdrawSolProperties, SplWithdrawStakeProperties,
+    },
+    utils::spl::{self, StakePool},
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, stake},
+};
+
+/// A pool run by the SPL stake pool program, such as Blaze, valued in its pool token.
+pub struct SplStakePoolAdapter {
+    pub stake_pool: StakePool,
+    /// The current epoch, against which the pool's last update is checked when valuing holdings
+    pub epoch: u64,
+}
+
+impl SplStakePoolAdapter {
+    pub fn load(stake_pool: &AccountInfo) -> Result<Self> {
+        Ok(Self {
+            stake_pool: spl::deserialize_spl_stake_pool(stake_pool)?,
+            epoch: Clock::get()?.epoch,
+        })
+    }
+}
+
+impl<'info> StakePoolAdapter<'info> for SplStakePoolAdapter {
+    type DepositSolAccounts = SplDepositSolProperties<'info>;
+    type WithdrawSolAccounts = SplWithdrawSolProperties<'info>;
+    type WithdrawStakeAccounts = SplWithdrawStakeProperties<'info>;
+    type DelayedUnstakeAccounts = SplWithdrawStakeProperties<'info>;
+
+    /// Values the holdings net of withdrawal fees, and conservatively if the pool is out of date.
+    fn value_of_holdings(&self, pool_tokens: u64) -> Result<u64> {
+        spl::calc_bsol_holdings_value(&self.stake_pool, pool_tokens, self.epoch)
+    }
+
+    fn deposit_sol(&self, accounts: &Self::DepositSolAccounts, lamports: u64) -> Result<()> {
+        deposit_sol_to_pool(accounts, lamports)
+    }
+
+    fn withdraw_sol(&self, accounts: &Self::WithdrawSolAccounts, lamports: u64) -> Result<()> {
+        withdraw_sol_from_pool(accounts, lamports)
+    }
+
+    fn withdraw_stake(&self, accounts: &Self::WithdrawStakeAccounts, lamports: u64) -> Result<()> {
+        withdraw_stake_from_pool(accounts, lamports)
+    }
+
+    /// Withdraws a stake account to the recipient and deactivates it.
+    /// The recipient is the stake authority of the new stake account, so must sign.
+    fn delayed_unstake(
+        &self,
+        accounts: &Self::DelayedUnstakeAccounts,
+        lamports: u64,
+    ) -> Result<()> {
+        withdraw_stake_from_pool(accounts, lamports)?;
+
+        msg!("Deactivating stake account");
+        let deactivate_ix = stake::instruction::deactivate_stake(
+            accounts.new_stake_account.key,
+            accounts.recipient.key,
+        );
+        invoke(
+            &deactivate_ix,
+            &[
+                accounts.new_stake_account.clone(),
+                accounts.sysvar_clock.clone(),
+                accounts.recipient.clone(),
+            ],
+        )?;
+        Ok(())
+    }
+}
@@ -54,4 +54,80 @@ pub enum ErrorCode {
     StakeAccountNotFullyDeactivated,
     #[msg("Invalid stake account state")]
     InvalidStakeAccountState,
+    #[msg("Basis points value must not exceed 10000")]
+    InvalidBasisPoints,
+    #[msg("The amount of gSOL minted is below the requested minimum")]
+    MinGsolOutNotMet,
+    #[msg("The share of the deposit routed to the liquidity pool exceeds the requested maximum")]
+    LiqPoolShareExceeded,
+    #[msg("The amount of SOL received is below the requested minimum")]
+    MinLamportsOutNotMet,
+    #[msg("An account required for this operation was not provided")]
+    MissingAccount,
+    #[msg("Too many yield recipients")]
+    TooManyYieldRecipients,
+    #[msg("Yield recipient weights must add up to 10000 basis points")]
+    InvalidYieldRecipientWeights,
+    #[msg("The extraction mode is not supported for this operation")]
+    UnsupportedExtractionMode,
+    #[msg("The estimated unstake fee is out of bounds")]
+    EstimatedUnstakeFeeOutOfBounds,
+    #[msg("The recovered margin is out of bounds")]
+    RecoveredMarginOutOfBounds,
+    #[msg("The update authority can only be changed with propose_update_authority and accept_update_authority")]
+    UpdateAuthorityChangeNotAllowed,
+    #[msg("The signer is not the pending update authority")]
+    NotPendingUpdateAuthority,
+    #[msg("The signer is not the operator or update authority")]
+    InvalidOperator,
+    #[msg("This operation is paused")]
+    OperationPaused,
+    #[msg("The signer is not the pause guardian or update authority")]
+    InvalidPauseGuardian,
+    #[msg("Unknown operation flags")]
+    InvalidOperationFlags,
+    #[msg("The state update delay is out of bounds")]
+    StateUpdateDelayOutOfBounds,
+    #[msg("This change must be queued with queue_state_update")]
+    StateUpdateRequiresTimelock,
+    #[msg("The queued state update is not yet executable")]
+    StateUpdateNotReady,
+    #[msg("The queued state update does not allow enough notice")]
+    StateUpdateEtaTooEarly,
+    #[msg("The deposit would take the gSOL supply above its cap")]
+    GsolSupplyCapExceeded,
+    #[msg("The deposit would exceed the deposit limit for this epoch")]
+    EpochDepositLimitExceeded,
+    #[msg("The target allocations must be set together and add up to 10000 basis points")]
+    InvalidTargetAllocation,
+    #[msg("No target allocation has been set for routing deposits")]
+    TargetAllocationNotSet,
+    #[msg("This operation is not supported by the stake pool")]
+    UnsupportedPoolOperation,
+    #[msg("The stake pool is already registered")]
+    PoolAlreadyRegistered,
+    #[msg("The stake pool is not registered")]
+    PoolNotRegistered,
+    #[msg("Too many registered stake pools")]
+    TooManyRegisteredPools,
+    #[msg("The stake pool is not enabled for deposits")]
+    PoolNotEnabled,
+    #[msg("The account is not this instance's authority for the stake pool's tokens")]
+    InvalidPoolAuthority,
+    #[msg("The registered pool is of a different type")]
+    InvalidPoolType,
+    #[msg("The Blaze pool is tracked on the state account and has no pool entry")]
+    BlazePoolHasNoEntry,
+    #[msg("The account does not match the one recorded by the stake pool")]
+    InvalidStakePoolAccount,
+    #[msg("The account does not match the one recorded by marinade")]
+    InvalidMarinadeAccount,
+    #[msg("The epoch report account is required while a per-epoch deposit limit is set")]
+    EpochReportRequired,
+    #[msg("Only timelocked fields can be queued, use update_state for the others")]
+    StateUpdateNotTimelocked,
+    #[msg("The pool registry must be passed once it has been created")]
+    MissingPoolRegistry,
+    #[msg("Deposits into a pool with an entry must go to its pool token account")]
+    InvalidPoolTokenAccount,
 }
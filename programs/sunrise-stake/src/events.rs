@@ -0,0 +1,224 @@
+use crate::instructions::ExtractionMode;
+use crate::state::{EpochReportAccount, LockAccount, YieldRecipient};
+use anchor_lang::prelude::*;
+
+/// SOL or a stake account was deposited and gSOL minted in return
+#[event]
+pub struct DepositEvent {
+    pub state: Pubkey,
+    pub depositor: Pubkey,
+    /// The Marinade state or SPL stake pool that received the deposit
+    pub pool: Pubkey,
+    pub lamports: u64,
+    pub gsol_minted: u64,
+}
+
+/// gSOL was burned in exchange for SOL, paid out immediately
+#[event]
+pub struct LiquidUnstakeEvent {
+    pub state: Pubkey,
+    pub staker: Pubkey,
+    pub gsol_burned: u64,
+    /// The portion of the unstake paid out of Marinade (liquidity pool or liquid unstake)
+    pub marinade_lamports: u64,
+    /// The portion of the unstake paid out of the Blaze pool reserve
+    pub blaze_lamports: u64,
+}
+
+/// gSOL was burned in exchange for a delayed unstake
+#[event]
+pub struct OrderUnstakeEvent {
+    pub state: Pubkey,
+    pub staker: Pubkey,
+    /// The sunrise ticket, blaze unstake ticket, or the stake account withdrawn to
+    pub ticket: Pubkey,
+    pub gsol_burned: u64,
+    pub lamports: u64,
+}
+
+/// A delayed unstake ticket was claimed
+#[event]
+pub struct TicketClaimedEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub beneficiary: Pubkey,
+    pub lamports: u64,
+}
+
+/// Yield was extracted to the treasury or a yield recipient
+#[event]
+pub struct YieldExtractedEvent {
+    pub state: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub mode: ExtractionMode,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockAction {
+    Lock,
+    AddLocked,
+    Unlock,
+    Update,
+    Init,
+}
+
+/// A lock account was changed
+#[event]
+pub struct LockEvent {
+    pub state: Pubkey,
+    pub lock_account: Pubkey,
+    pub authority: Pubkey,
+    pub action: LockAction,
+    /// The gSOL moved into or out of the lock account (zero for updates)
+    pub lamports: u64,
+    pub yield_accrued_by_owner: u64,
+}
+
+impl LockEvent {
+    pub fn new(lock_account: &Account<LockAccount>, action: LockAction, lamports: u64) -> Self {
+        Self {
+            state: lock_account.state_address,
+            lock_account: lock_account.key(),
+            authority: lock_account.owner,
+            action,
+            lamports,
+            yield_accrued_by_owner: lock_account.yield_accrued_by_owner,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebalanceAction {
+    OrderDelayedUnstake,
+    RecoverTickets,
+    MoveSplLiquidToMarinade,
+    CreateSplStakeAccount,
+    DepositSplStakeToLiquid,
+}
+
+/// Funds were moved between the pools or the Marinade liquidity pool
+#[event]
+pub struct RebalanceEvent {
+    pub state: Pubkey,
+    pub action: RebalanceAction,
+    pub lamports: u64,
+}
+
+/// Operations were paused or unpaused
+#[event]
+pub struct PauseEvent {
+    pub state: Pubkey,
+    /// The pause guardian or update authority that made the change
+    pub authority: Pubkey,
+    /// The full set of paused operations after the change
+    pub paused_operations: u16,
+}
+
+/// A queued change to the state was cancelled before it was executed
+#[event]
+pub struct StateUpdateCancelledEvent {
+    pub state: Pubkey,
+    pub pending_state_change: Pubkey,
+}
+
+/// A change to the state was queued, to be executed no earlier than `eta`
+#[event]
+pub struct StateUpdateQueuedEvent {
+    pub state: Pubkey,
+    pub pending_state_change: Pubkey,
+    pub eta: i64,
+}
+
+/// The state account was registered or updated
+#[event]
+pub struct StateUpdatedEvent {
+    pub state: Pubkey,
+    pub update_authority: Pubkey,
+}
+
+/// The state account was migrated to the current layout
+#[event]
+pub struct StateMigratedEvent {
+    pub state: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TicketTransferAction {
+    Transfer,
+    Tokenize,
+    RedeemReceipt,
+}
+
+/// The beneficiary of a sunrise unstake ticket changed
+#[event]
+pub struct TicketTransferredEvent {
+    pub state: Pubkey,
+    pub ticket: Pubkey,
+    pub previous_beneficiary: Pubkey,
+    /// The new beneficiary, or the receipt mint if the ticket was tokenized
+    pub beneficiary: Pubkey,
+    pub action: TicketTransferAction,
+}
+
+/// The recipients that extracted yield is split between were set
+#[event]
+pub struct YieldRecipientsUpdatedEvent {
+    pub state: Pubkey,
+    pub recipients: Vec<YieldRecipient>,
+}
+
+/// The epoch report was created or brought up to date
+#[event]
+pub struct EpochReportUpdatedEvent {
+    pub state: Pubkey,
+    pub epoch: u64,
+    pub extractable_yield: u64,
+    pub extracted_yield: u64,
+    pub current_gsol_supply: u64,
+}
+
+impl From<&EpochReportAccount> for EpochReportUpdatedEvent {
+    fn from(report: &EpochReportAccount) -> Self {
+        Self {
+            state: report.state_address,
+            epoch: report.epoch,
+            extractable_yield: report.extractable_yield,
+            extracted_yield: report.extracted_yield,
+            current_gsol_supply: report.current_gsol_supply,
+        }
+    }
+}
+
+/// The epoch report account was resized to the current layout
+#[event]
+pub struct EpochReportMigratedEvent {
+    pub state: Pubkey,
+    pub epoch_report_account: Pubkey,
+    pub space: u64,
+}
+
+/// The account keeping past epoch reports was created
+#[event]
+pub struct EpochReportHistoryCreatedEvent {
+    pub state: Pubkey,
+    pub epoch_report_history: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolAction {
+    Register,
+    Enable,
+    Disable,
+    InitEntry,
+}
+
+/// A pool in the registry was added, enabled or disabled, or given an entry
+#[event]
+pub struct PoolEvent {
+    pub state: Pubkey,
+    pub pool: Pubkey,
+    pub action: PoolAction,
+}
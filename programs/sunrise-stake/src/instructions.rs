@@ -1,19 +1,44 @@
+mod accept_update_authority;
 mod add_locked_gsol;
+mod cancel_state_update;
 mod claim_unstake_ticket;
+mod claim_unstake_ticket_on_behalf;
+mod claim_unstake_tickets;
 mod create_metadata;
 mod create_spl_stake_account;
 mod deposit;
+mod deposit_routed;
 mod deposit_spl_stake_to_liquid;
 mod deposit_stake_account;
+mod execute_state_update;
 mod extract_to_treasury;
+mod extract_to_treasury_v2;
+mod extract_to_yield_recipients;
+mod get_reserves;
 mod init_epoch_report;
+mod init_epoch_report_history;
 mod init_lock_account;
+mod init_pool_entry;
 mod liquid_unstake;
 mod lock_gsol;
+mod migrate_epoch_report;
+mod migrate_state;
 mod move_spl_liquid_to_marinade;
 mod order_unstake;
+mod pause;
+mod propose_update_authority;
+mod queue_state_update;
 mod recover_tickets;
+mod redeem_unstake_ticket_receipt;
+mod register_pool;
 mod register_state;
+mod set_operator;
+mod set_pause_guardian;
+mod set_paused_operations;
+mod set_pool_enabled;
+mod set_yield_recipients;
+mod tokenize_unstake_ticket;
+mod transfer_unstake_ticket;
 mod trigger_pool_rebalance;
 mod unlock_gsol;
 mod update_epoch_report;
@@ -22,22 +47,47 @@ mod update_lock_account_without_nft;
 mod update_metadata;
 mod update_state;
 
+pub use accept_update_authority::*;
 pub use add_locked_gsol::*;
+pub use cancel_state_update::*;
 pub use claim_unstake_ticket::*;
+pub use claim_unstake_ticket_on_behalf::*;
+pub use claim_unstake_tickets::*;
 pub use create_metadata::*;
 pub use create_spl_stake_account::*;
 pub use deposit::*;
+pub use deposit_routed::*;
 pub use deposit_spl_stake_to_liquid::*;
 pub use deposit_stake_account::*;
+pub use execute_state_update::*;
 pub use extract_to_treasury::*;
+pub use extract_to_treasury_v2::*;
+pub use extract_to_yield_recipients::*;
+pub use get_reserves::*;
 pub use init_epoch_report::*;
+pub use init_epoch_report_history::*;
 pub use init_lock_account::*;
+pub use init_pool_entry::*;
 pub use liquid_unstake::*;
 pub use lock_gsol::*;
+pub use migrate_epoch_report::*;
+pub use migrate_state::*;
 pub use move_spl_liquid_to_marinade::*;
 pub use order_unstake::*;
+pub use pause::*;
+pub use propose_update_authority::*;
+pub use queue_state_update::*;
 pub use recover_tickets::*;
+pub use redeem_unstake_ticket_receipt::*;
+pub use register_pool::*;
 pub use register_state::*;
+pub use set_operator::*;
+pub use set_pause_guardian::*;
+pub use set_paused_operations::*;
+pub use set_pool_enabled::*;
+pub use set_yield_recipients::*;
+pub use tokenize_unstake_ticket::*;
+pub use transfer_unstake_ticket::*;
 pub use trigger_pool_rebalance::*;
 pub use unlock_gsol::*;
 pub use update_epoch_report::*;
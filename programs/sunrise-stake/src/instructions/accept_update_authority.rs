@@ -0,0 +1,26 @@
+use crate::events::StateUpdatedEvent;
+use crate::state::State;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptUpdateAuthority<'info> {
+    #[account(mut)]
+    pub state: Account<'info, State>,
+
+    /// The key proposed in propose_update_authority
+    pub new_update_authority: Signer<'info>,
+}
+
+pub fn accept_update_authority_handler(ctx: Context<AcceptUpdateAuthority>) -> Result<()> {
+    let new_update_authority = ctx.accounts.new_update_authority.key();
+    ctx.accounts
+        .state
+        .accept_update_authority(&new_update_authority)?;
+
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: new_update_authority,
+    });
+    Ok(())
+}
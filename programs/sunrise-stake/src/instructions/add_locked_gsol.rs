@@ -1,10 +1,12 @@
 use crate::error::ErrorCode;
-use crate::state::{EpochReportAccount, LockAccount, State};
+use crate::events::{LockAction, LockEvent};
+use crate::state::{operation, EpochReportAccount, LockAccount, State};
 use crate::utils::seeds::{EPOCH_REPORT_ACCOUNT, LOCK_TOKEN_ACCOUNT};
 use crate::utils::token::transfer_to;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 #[instruction(lamports: u64)]
 pub struct AddLockedGSol<'info> {
@@ -59,9 +61,11 @@ pub struct AddLockedGSol<'info> {
 }
 
 pub fn add_locked_gsol_handler(ctx: Context<AddLockedGSol>, lamports: u64) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::LOCK)?;
     ctx.accounts.lock_account.calculate_and_add_yield_accrued(
         &ctx.accounts.epoch_report_account,
         &ctx.accounts.lock_gsol_account,
+        ctx.accounts.state.estimated_unstake_fee_bps,
     )?;
 
     transfer_to(
@@ -72,5 +76,10 @@ pub fn add_locked_gsol_handler(ctx: Context<AddLockedGSol>, lamports: u64) -> Re
         &ctx.accounts.token_program,
     )?;
 
+    emit_cpi!(LockEvent::new(
+        &ctx.accounts.lock_account,
+        LockAction::AddLocked,
+        lamports
+    ));
     Ok(())
 }
@@ -0,0 +1,32 @@
+use crate::events::StateUpdateCancelledEvent;
+use crate::state::{PendingStateChange, State};
+use crate::utils::seeds::PENDING_STATE_CHANGE;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelStateUpdate<'info> {
+    #[account(
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = update_authority,
+        seeds = [state.key().as_ref(), PENDING_STATE_CHANGE],
+        bump = pending_state_change.bump,
+    )]
+    pub pending_state_change: Box<Account<'info, PendingStateChange>>,
+}
+
+pub fn cancel_state_update_handler(ctx: Context<CancelStateUpdate>) -> Result<()> {
+    emit_cpi!(StateUpdateCancelledEvent {
+        state: ctx.accounts.state.key(),
+        pending_state_change: ctx.accounts.pending_state_change.key(),
+    });
+    Ok(())
+}
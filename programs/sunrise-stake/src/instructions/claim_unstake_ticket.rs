@@ -1,12 +1,13 @@
+use crate::events::TicketClaimedEvent;
 use crate::marinade::accounts::TicketAccountData as MarinadeTicketAccount;
 use crate::marinade::program::MarinadeFinance;
 use crate::state::{State, SunriseTicketAccount};
-use crate::utils::marinade;
 use crate::utils::seeds::MSOL_ACCOUNT;
+use crate::utils::{marinade, system};
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke_signed, system_instruction::transfer};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct ClaimUnstakeTicket<'info> {
     #[account(
@@ -53,23 +54,18 @@ pub fn claim_unstake_ticket_handler(ctx: Context<ClaimUnstakeTicket>) -> Result<
     marinade::claim_unstake_ticket(&accounts)?;
 
     // transfer the released SOL to the beneficiary
-    let lamports = ctx.accounts.marinade_ticket_account.lamports_amount;
-    let ix = transfer(
-        &ctx.accounts.msol_authority.key(),
-        &ctx.accounts.transfer_sol_to.key(),
-        lamports,
-    );
-    let bump = &[ctx.accounts.state.msol_authority_bump][..];
-    let state_address = ctx.accounts.state.key();
-    let seeds = &[state_address.as_ref(), MSOL_ACCOUNT, bump][..];
-    invoke_signed(
-        &ix,
-        &[
-            ctx.accounts.msol_authority.to_account_info(),
-            ctx.accounts.transfer_sol_to.to_account_info(),
-        ],
-        &[seeds],
+    system::transfer_from_msol_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.msol_authority.to_account_info(),
+        &ctx.accounts.transfer_sol_to.to_account_info(),
+        ctx.accounts.marinade_ticket_account.lamports_amount,
     )?;
 
+    emit_cpi!(TicketClaimedEvent {
+        state: ctx.accounts.state.key(),
+        ticket: ctx.accounts.sunrise_ticket_account.key(),
+        beneficiary: ctx.accounts.transfer_sol_to.key(),
+        lamports: ctx.accounts.marinade_ticket_account.lamports_amount,
+    });
     Ok(())
 }
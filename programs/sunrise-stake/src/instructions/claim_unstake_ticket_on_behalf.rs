@@ -0,0 +1,84 @@
+use crate::events::TicketClaimedEvent;
+use crate::marinade::accounts::TicketAccountData as MarinadeTicketAccount;
+use crate::marinade::program::MarinadeFinance;
+use crate::state::{State, SunriseTicketAccount};
+use crate::utils::seeds::MSOL_ACCOUNT;
+use crate::utils::{marinade, system};
+use anchor_lang::prelude::*;
+use std::ops::Deref;
+
+/// Permissionless version of ClaimUnstakeTicket.
+/// Anyone can claim a ticket once it is due. The released SOL and the rent of the
+/// sunrise ticket account are sent to the recorded beneficiary.
+/// Tokenized tickets (whose beneficiary is a receipt mint) must be redeemed first.
+#[event_cpi]
+#[derive(Accounts, Clone)]
+pub struct ClaimUnstakeTicketOnBehalf<'info> {
+    #[account(
+    has_one = marinade_state,
+    )]
+    pub state: Box<Account<'info, State>>,
+    /// CHECK: Validated in handler
+    #[account(mut)]
+    pub marinade_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub reserve_pda: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub marinade_ticket_account: Account<'info, MarinadeTicketAccount>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+        has_one = marinade_ticket_account,
+        has_one = beneficiary,
+        constraint = sunrise_ticket_account.state_address == state.key(),
+    )]
+    pub sunrise_ticket_account: Account<'info, SunriseTicketAccount>,
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), MSOL_ACCOUNT],
+    bump = state.msol_authority_bump
+    )]
+    pub msol_authority: SystemAccount<'info>, // sunrise-stake PDA
+
+    #[account(mut)]
+    pub beneficiary: SystemAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub marinade_program: Program<'info, MarinadeFinance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_unstake_ticket_on_behalf_handler(
+    ctx: Context<ClaimUnstakeTicketOnBehalf>,
+) -> Result<()> {
+    let accounts = ctx.accounts.deref().into();
+    marinade::claim_unstake_ticket(&accounts)?;
+
+    msg!(
+        "Claiming ticket {} on behalf of {}",
+        ctx.accounts.sunrise_ticket_account.key(),
+        ctx.accounts.beneficiary.key()
+    );
+
+    // transfer the released SOL to the beneficiary
+    system::transfer_from_msol_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.msol_authority.to_account_info(),
+        &ctx.accounts.beneficiary.to_account_info(),
+        ctx.accounts.marinade_ticket_account.lamports_amount,
+    )?;
+
+    emit_cpi!(TicketClaimedEvent {
+        state: ctx.accounts.state.key(),
+        ticket: ctx.accounts.sunrise_ticket_account.key(),
+        beneficiary: ctx.accounts.beneficiary.key(),
+        lamports: ctx.accounts.marinade_ticket_account.lamports_amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use crate::events::TicketClaimedEvent;
+use crate::marinade::accounts::TicketAccountData as MarinadeTicketAccount;
+use crate::marinade::program::MarinadeFinance;
+use crate::state::{State, SunriseTicketAccount};
+use crate::utils::marinade::ClaimUnstakeTicketProperties;
+use crate::utils::seeds::MSOL_ACCOUNT;
+use crate::utils::{marinade, system};
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+use std::ops::Deref;
+
+/// Claim several delayed unstake tickets belonging to the same beneficiary at once.
+/// The tickets are passed in the remaining accounts as pairs of
+/// (marinade ticket account, sunrise ticket account).
+/// The released SOL is sent to the beneficiary in a single transfer.
+#[event_cpi]
+#[derive(Accounts, Clone)]
+pub struct ClaimUnstakeTickets<'info> {
+    #[account(
+    has_one = marinade_state,
+    )]
+    pub state: Box<Account<'info, State>>,
+    /// CHECK: Validated in handler
+    #[account(mut)]
+    pub marinade_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub reserve_pda: UncheckedAccount<'info>,
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), MSOL_ACCOUNT],
+    bump = state.msol_authority_bump
+    )]
+    pub msol_authority: SystemAccount<'info>, // sunrise-stake PDA
+
+    #[account(mut)]
+    pub transfer_sol_to: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub marinade_program: Program<'info, MarinadeFinance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_unstake_tickets_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimUnstakeTickets<'info>>,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() % 2 == 0,
+        ErrorCode::UnexpectedAccounts
+    );
+
+    let state_address = ctx.accounts.state.key();
+    let beneficiary = ctx.accounts.transfer_sol_to.key();
+
+    let mut claimed_lamports: u64 = 0;
+    let mut props: ClaimUnstakeTicketProperties = ctx.accounts.deref().into();
+    msg!("Tickets to claim: {}", ctx.remaining_accounts.len() / 2);
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let marinade_ticket_account = Account::<MarinadeTicketAccount>::try_from(&pair[0])?;
+        let sunrise_ticket_account = Account::<SunriseTicketAccount>::try_from(&pair[1])?;
+
+        // The equivalent of the constraints on ClaimUnstakeTicket
+        require_keys_eq!(
+            sunrise_ticket_account.state_address,
+            state_address,
+            ErrorCode::UnexpectedAccounts
+        );
+        require_keys_eq!(
+            sunrise_ticket_account.marinade_ticket_account,
+            marinade_ticket_account.key(),
+            ErrorCode::UnexpectedAccounts
+        );
+        require_keys_eq!(
+            sunrise_ticket_account.beneficiary,
+            beneficiary,
+            ErrorCode::UnexpectedAccounts
+        );
+
+        msg!(
+            "Claiming ticket {} with value {}",
+            sunrise_ticket_account.key(),
+            marinade_ticket_account.lamports_amount
+        );
+
+        props.ticket_account = marinade_ticket_account.to_account_info();
+        marinade::claim_unstake_ticket(&props)?;
+
+        claimed_lamports = claimed_lamports
+            .checked_add(marinade_ticket_account.lamports_amount)
+            .unwrap();
+
+        emit_cpi!(TicketClaimedEvent {
+            state: state_address,
+            ticket: sunrise_ticket_account.key(),
+            beneficiary,
+            lamports: marinade_ticket_account.lamports_amount,
+        });
+
+        sunrise_ticket_account.close(ctx.accounts.transfer_sol_to.to_account_info())?;
+    }
+
+    // transfer the released SOL to the beneficiary
+    msg!("Claimed {} lamports", claimed_lamports);
+    system::transfer_from_msol_authority(
+        &ctx.accounts.state,
+        &ctx.accounts.msol_authority.to_account_info(),
+        &ctx.accounts.transfer_sol_to.to_account_info(),
+        claimed_lamports,
+    )
+}
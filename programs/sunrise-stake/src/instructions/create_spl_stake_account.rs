@@ -1,3 +1,5 @@
+use crate::error::ErrorCode;
+use crate::events::{RebalanceAction, RebalanceEvent};
 use crate::state::State;
 use crate::utils::{seeds, spl};
 use anchor_lang::{
@@ -14,20 +16,22 @@ const SPL_STAKE_POOL_ID: Pubkey =
     anchor_lang::solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
 
 /// Create a stake account from the SPL stake pool and deactivate it.
-/// This is an admin-only instruction for rebalancing funds between pools.
+/// This is an operator-only instruction for rebalancing funds between pools.
 /// The stake account will be deactivated and can be deposited to Marinade liq pool
 /// after it fully deactivates (next epoch boundary).
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(index: u64)]
 pub struct CreateSplStakeAccount<'info> {
     #[account(
         mut,
-        has_one = update_authority,
+        constraint = state.is_operator(operator.key) @ ErrorCode::InvalidOperator,
         constraint = state.blaze_state == *stake_pool.key
     )]
     pub state: Box<Account<'info, State>>,
 
-    pub update_authority: Signer<'info>,
+    /// The operator or update authority
+    pub operator: Signer<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -209,6 +213,11 @@ pub fn create_spl_stake_account_handler(
         state.blaze_minted_gsol
     );
 
+    emit_cpi!(RebalanceEvent {
+        state: ctx.accounts.state.key(),
+        action: RebalanceAction::CreateSplStakeAccount,
+        lamports,
+    });
     Ok(())
 }
 
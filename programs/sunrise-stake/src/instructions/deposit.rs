@@ -1,6 +1,10 @@
+use crate::adapters::{MarinadeAdapter, StakePoolAdapter};
+use crate::error::ErrorCode;
+use crate::events::DepositEvent;
 use crate::marinade::program::MarinadeFinance;
-use crate::state::State;
-use crate::utils::seeds::{GSOL_MINT_AUTHORITY, MSOL_ACCOUNT};
+use crate::state::{operation, EpochReportAccount, PoolRegistry, PoolType, State};
+use crate::utils::calc::{proportional, MAX_BASIS_POINTS};
+use crate::utils::seeds::{EPOCH_REPORT_ACCOUNT, GSOL_MINT_AUTHORITY, MSOL_ACCOUNT, POOL_REGISTRY};
 use crate::utils::token::mint_to;
 use crate::utils::{marinade, marinade::amount_to_be_deposited_in_liq_pool};
 use anchor_lang::prelude::*;
@@ -8,6 +12,7 @@ use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct Deposit<'info> {
     #[account(mut,has_one = marinade_state)]
@@ -87,37 +92,142 @@ pub struct Deposit<'info> {
     )]
     pub msol_token_account_authority: SystemAccount<'info>,
 
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Option<Box<Account<'info, EpochReportAccount>>>,
+
+    /// Required once the registry has been created, see State::check_accepts_deposits
+    #[account(
+    seeds = [state.key().as_ref(), POOL_REGISTRY],
+    bump = pool_registry.bump,
+    )]
+    pub pool_registry: Option<Box<Account<'info, PoolRegistry>>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub marinade_program: Program<'info, MarinadeFinance>,
 }
 
 pub fn deposit_handler(ctx: Context<Deposit>, lamports: u64) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::DEPOSIT)?;
+    ctx.accounts.state.check_accepts_deposits(
+        ctx.accounts
+            .pool_registry
+            .as_deref()
+            .map(|registry| &**registry),
+        ctx.accounts.marinade_state.key,
+        PoolType::Marinade,
+    )?;
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        lamports,
+    )?;
     msg!("Checking liq_pool pool balance");
     let to_deposit_in_liq_pool = amount_to_be_deposited_in_liq_pool(ctx.accounts, lamports)?;
+    deposit_to_marinade(ctx.accounts, lamports, to_deposit_in_liq_pool)?;
+
+    emit_cpi!(deposit_event(ctx.accounts, lamports));
+    Ok(())
+}
+
+/// Deposit with caller-supplied bounds.
+/// `min_gsol_out` is the minimum amount of gSOL that must be minted to `mint_gsol_to`,
+/// and `max_liq_pool_share` (in basis points) caps the portion of `lamports`
+/// that may be routed to the Marinade liquidity pool.
+pub fn deposit_v2_handler(
+    ctx: Context<Deposit>,
+    lamports: u64,
+    min_gsol_out: u64,
+    max_liq_pool_share: u16,
+) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::DEPOSIT)?;
+    ctx.accounts.state.check_accepts_deposits(
+        ctx.accounts
+            .pool_registry
+            .as_deref()
+            .map(|registry| &**registry),
+        ctx.accounts.marinade_state.key,
+        PoolType::Marinade,
+    )?;
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        lamports,
+    )?;
+    require_gte!(
+        MAX_BASIS_POINTS,
+        max_liq_pool_share as u64,
+        ErrorCode::InvalidBasisPoints
+    );
+
+    msg!("Checking liq_pool pool balance");
+    let to_deposit_in_liq_pool = amount_to_be_deposited_in_liq_pool(ctx.accounts, lamports)?;
+    let max_to_deposit_in_liq_pool =
+        proportional(lamports, max_liq_pool_share as u64, MAX_BASIS_POINTS)?;
+    require_gte!(
+        max_to_deposit_in_liq_pool,
+        to_deposit_in_liq_pool,
+        ErrorCode::LiqPoolShareExceeded
+    );
+
+    let gsol_balance_before = ctx.accounts.mint_gsol_to.amount;
+    deposit_to_marinade(ctx.accounts, lamports, to_deposit_in_liq_pool)?;
+
+    ctx.accounts.mint_gsol_to.reload()?;
+    let gsol_out = ctx
+        .accounts
+        .mint_gsol_to
+        .amount
+        .checked_sub(gsol_balance_before)
+        .ok_or(ErrorCode::InvalidCalculation)?;
+    require_gte!(gsol_out, min_gsol_out, ErrorCode::MinGsolOutNotMet);
+
+    emit_cpi!(deposit_event(ctx.accounts, lamports));
+    Ok(())
+}
+
+fn deposit_event(accounts: &Deposit, lamports: u64) -> DepositEvent {
+    DepositEvent {
+        state: accounts.state.key(),
+        depositor: accounts.transfer_from.key(),
+        pool: accounts.marinade_state.key(),
+        lamports,
+        gsol_minted: lamports,
+    }
+}
+
+pub(crate) fn deposit_to_marinade(
+    accounts: &mut Deposit,
+    lamports: u64,
+    to_deposit_in_liq_pool: u64,
+) -> Result<()> {
     let to_stake = lamports - to_deposit_in_liq_pool;
 
     if to_deposit_in_liq_pool > 0 {
         msg!("Depositing {} in liq_pool pool", to_deposit_in_liq_pool);
-        let accounts = ctx.accounts.deref().into();
-        marinade::add_liquidity(&accounts, to_deposit_in_liq_pool)?;
+        let add_liquidity_accounts = accounts.deref().into();
+        marinade::add_liquidity(&add_liquidity_accounts, to_deposit_in_liq_pool)?;
     }
 
     if to_stake > 0 {
         msg!("Staking {}", to_stake);
-        marinade::deposit(ctx.accounts, to_stake)?;
+        MarinadeAdapter::load(&accounts.marinade_state)?.deposit_sol(accounts, to_stake)?;
     }
 
     msg!("Mint {} GSOL", lamports);
     mint_to(
         lamports,
-        &ctx.accounts.gsol_mint.to_account_info(),
-        &ctx.accounts.gsol_mint_authority.to_account_info(),
-        &ctx.accounts.mint_gsol_to.to_account_info(),
-        &ctx.accounts.token_program.to_account_info(),
-        &ctx.accounts.state,
+        &accounts.gsol_mint.to_account_info(),
+        &accounts.gsol_mint_authority.to_account_info(),
+        &accounts.mint_gsol_to.to_account_info(),
+        &accounts.token_program.to_account_info(),
+        &accounts.state,
     )?;
-    let state = &mut ctx.accounts.state;
+    let state = &mut accounts.state;
     state.marinade_minted_gsol = state.marinade_minted_gsol.checked_add(lamports).unwrap();
     Ok(())
 }
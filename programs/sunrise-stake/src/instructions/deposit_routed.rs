@@ -0,0 +1,275 @@
+use crate::error::ErrorCode;
+use crate::events::DepositEvent;
+use crate::instructions::deposit_to_marinade;
+use crate::marinade::program::MarinadeFinance;
+use crate::state::{operation, DepositPool, EpochReportAccount, PoolRegistry, PoolType, State};
+use crate::utils::marinade::amount_to_be_deposited_in_liq_pool;
+use crate::utils::seeds::{
+    BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, GSOL_MINT_AUTHORITY, MSOL_ACCOUNT, POOL_REGISTRY,
+};
+use crate::{Deposit, SplDepositSol};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Deposit SOL into whichever of Marinade and Blaze is furthest below its target allocation.
+/// Takes the accounts needed to deposit into either pool.
+#[event_cpi]
+#[derive(Accounts, Clone)]
+pub struct DepositRouted<'info> {
+    #[account(
+        mut,
+        has_one = marinade_state,
+        has_one = gsol_mint,
+        constraint = state.blaze_state == *stake_pool.key
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(
+    mut,
+    constraint = gsol_mint.mint_authority == COption::Some(gsol_mint_authority.key()),
+    )]
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+    seeds = [
+    state.key().as_ref(),
+    GSOL_MINT_AUTHORITY,
+    ],
+    bump = state.gsol_mint_authority_bump,
+    )]
+    pub gsol_mint_authority: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(
+    mut,
+    token::mint = gsol_mint,
+    )]
+    pub mint_gsol_to: Account<'info, TokenAccount>,
+
+    ///////////////////////////////////////////////////////
+    ///  Marinade Accounts
+    /// //////////////////////////////////////////////////
+    /// CHECK: Validated in handler
+    #[account(mut)]
+    pub marinade_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub liq_pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Checked in marinade program
+    pub liq_pool_msol_leg_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked in marinade program
+    pub liq_pool_mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub reserve_pda: AccountInfo<'info>,
+
+    #[account(
+    mut,
+    token::mint = msol_mint,
+    token::authority = msol_token_account_authority,
+    )]
+    pub mint_msol_to: Account<'info, TokenAccount>,
+
+    #[account(
+    mut,
+    token::mint = liq_pool_mint,
+    token::authority = msol_token_account_authority,
+    )]
+    pub mint_liq_pool_to: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Checked in marinade program
+    pub msol_mint_authority: AccountInfo<'info>,
+
+    #[account(
+    seeds = [state.key().as_ref(), MSOL_ACCOUNT],
+    bump = state.msol_authority_bump
+    )]
+    pub msol_token_account_authority: SystemAccount<'info>,
+
+    pub marinade_program: Program<'info, MarinadeFinance>,
+
+    ///////////////////////////////////////////////////////
+    ///  Blaze Stake Accounts
+    /// //////////////////////////////////////////////////
+    #[account(
+        mut,
+        token::mint = stake_pool_token_mint,
+        token::authority = bsol_account_authority,
+    )]
+    pub bsol_token_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [state.key().as_ref(), BSOL_ACCOUNT],
+        bump = state.bsol_authority_bump
+    )]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub bsol_account_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub stake_pool: AccountInfo<'info>,
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub stake_pool_withdraw_authority: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub reserve_stake_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub manager_fee_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub stake_pool_token_mint: AccountInfo<'info>,
+    /// CHECK: Checked against the SPL stake pool program id in SplDepositSol::deposit_sol
+    pub stake_pool_program: AccountInfo<'info>,
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Option<Box<Account<'info, EpochReportAccount>>>,
+
+    /// Required once the registry has been created, see State::check_accepts_deposits
+    #[account(
+    seeds = [state.key().as_ref(), POOL_REGISTRY],
+    bump = pool_registry.bump,
+    )]
+    pub pool_registry: Option<Box<Account<'info, PoolRegistry>>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DepositRouted<'info> {
+    fn marinade_deposit(&self) -> Deposit<'info> {
+        Deposit {
+            state: self.state.clone(),
+            marinade_state: self.marinade_state.clone(),
+            gsol_mint: self.gsol_mint.clone(),
+            gsol_mint_authority: self.gsol_mint_authority.clone(),
+            msol_mint: self.msol_mint.clone(),
+            liq_pool_mint: self.liq_pool_mint.clone(),
+            liq_pool_sol_leg_pda: self.liq_pool_sol_leg_pda.clone(),
+            liq_pool_msol_leg: self.liq_pool_msol_leg.clone(),
+            liq_pool_msol_leg_authority: self.liq_pool_msol_leg_authority.clone(),
+            liq_pool_mint_authority: self.liq_pool_mint_authority.clone(),
+            reserve_pda: self.reserve_pda.clone(),
+            transfer_from: self.depositor.clone(),
+            mint_msol_to: self.mint_msol_to.clone(),
+            mint_liq_pool_to: self.mint_liq_pool_to.clone(),
+            mint_gsol_to: self.mint_gsol_to.clone(),
+            msol_mint_authority: self.msol_mint_authority.clone(),
+            msol_token_account_authority: self.msol_token_account_authority.clone(),
+            system_program: self.system_program.clone(),
+            token_program: self.token_program.clone(),
+            marinade_program: self.marinade_program.clone(),
+            epoch_report_account: self.epoch_report_account.clone(),
+            pool_registry: self.pool_registry.clone(),
+            event_authority: self.event_authority.clone(),
+            program: self.program.clone(),
+        }
+    }
+
+    fn blaze_deposit(&self) -> SplDepositSol<'info> {
+        SplDepositSol {
+            state: self.state.clone(),
+            gsol_mint: self.gsol_mint.clone(),
+            gsol_mint_authority: self.gsol_mint_authority.clone(),
+            depositor: self.depositor.clone(),
+            depositor_gsol_token_account: self.mint_gsol_to.clone(),
+            bsol_token_account: self.bsol_token_account.clone(),
+            bsol_account_authority: self.bsol_account_authority.clone(),
+            stake_pool: self.stake_pool.clone(),
+            pool_entry: None,
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority.clone(),
+            reserve_stake_account: self.reserve_stake_account.clone(),
+            manager_fee_account: self.manager_fee_account.clone(),
+            stake_pool_token_mint: self.stake_pool_token_mint.clone(),
+            stake_pool_program: self.stake_pool_program.clone(),
+            epoch_report_account: self.epoch_report_account.clone(),
+            pool_registry: self.pool_registry.clone(),
+            system_program: self.system_program.clone(),
+            token_program: self.token_program.clone(),
+            event_authority: self.event_authority.clone(),
+            program: self.program.clone(),
+        }
+    }
+}
+
+/// Deposit SOL into the pool furthest below its target allocation,
+/// failing if fewer than `min_gsol_out` gSOL are minted.
+pub fn deposit_routed_handler(
+    ctx: Context<DepositRouted>,
+    lamports: u64,
+    min_gsol_out: u64,
+) -> Result<()> {
+    let state = &ctx.accounts.state;
+    let pool_registry = ctx
+        .accounts
+        .pool_registry
+        .as_deref()
+        .map(|registry| &**registry);
+    let accepts_deposits = |pool: &Pubkey, pool_type: PoolType| -> Result<bool> {
+        match state.check_accepts_deposits(pool_registry, pool, pool_type) {
+            Ok(()) => Ok(true),
+            // a missing registry is the caller's mistake, not a reason to route elsewhere
+            Err(error) if error == ErrorCode::MissingPoolRegistry.into() => Err(error),
+            Err(_) => Ok(false),
+        }
+    };
+    let marinade_enabled = accepts_deposits(&state.marinade_state, PoolType::Marinade)?;
+    let blaze_enabled = accepts_deposits(&state.blaze_state, PoolType::Spl)?;
+    let pool = state.deposit_route(marinade_enabled, blaze_enabled)?;
+    msg!("Routing deposit of {} lamports to {:?}", lamports, pool);
+
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        lamports,
+    )?;
+
+    let (pool_address, gsol_minted) = match pool {
+        DepositPool::Marinade => {
+            ctx.accounts.state.check_not_paused(operation::DEPOSIT)?;
+            let mut accounts = ctx.accounts.marinade_deposit();
+            let to_deposit_in_liq_pool = amount_to_be_deposited_in_liq_pool(&accounts, lamports)?;
+            deposit_to_marinade(&mut accounts, lamports, to_deposit_in_liq_pool)?;
+            // the state is cloned into the Marinade accounts, so copy back the updated accounting
+            ctx.accounts.state.marinade_minted_gsol = accounts.state.marinade_minted_gsol;
+            (ctx.accounts.marinade_state.key(), lamports)
+        }
+        DepositPool::Blaze => {
+            ctx.accounts
+                .state
+                .check_not_paused(operation::SPL_DEPOSIT)?;
+            let mut accounts = ctx.accounts.blaze_deposit();
+            let gsol_minted = accounts.deposit_sol(lamports)?;
+            ctx.accounts.state.blaze_minted_gsol = accounts.state.blaze_minted_gsol;
+            (ctx.accounts.stake_pool.key(), gsol_minted)
+        }
+    };
+    require_gte!(gsol_minted, min_gsol_out, ErrorCode::MinGsolOutNotMet);
+
+    emit_cpi!(DepositEvent {
+        state: ctx.accounts.state.key(),
+        depositor: ctx.accounts.depositor.key(),
+        pool: pool_address,
+        lamports,
+        gsol_minted,
+    });
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::events::{RebalanceAction, RebalanceEvent};
 use crate::marinade::program::MarinadeFinance;
 use crate::state::State;
 use crate::utils::{marinade, seeds};
@@ -14,19 +15,21 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
 /// Deposit a deactivated stake account (from SPL rebalancing) into Marinade liquidity pool.
-/// This is an admin-only instruction for rebalancing funds between pools.
+/// This is an operator-only instruction for rebalancing funds between pools.
 /// The stake account must be fully deactivated before this can be called.
+#[event_cpi]
 #[derive(Accounts, Clone)]
 #[instruction(index: u64)]
 pub struct DepositSplStakeToLiquid<'info> {
     #[account(
         mut,
-        has_one = update_authority,
+        constraint = state.is_operator(operator.key) @ ErrorCode::InvalidOperator,
         has_one = marinade_state,
     )]
     pub state: Box<Account<'info, State>>,
 
-    pub update_authority: Signer<'info>,
+    /// The operator or update authority
+    pub operator: Signer<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -155,6 +158,11 @@ pub fn deposit_spl_stake_to_liquid_handler(
         state.marinade_minted_gsol
     );
 
+    emit_cpi!(RebalanceEvent {
+        state: ctx.accounts.state.key(),
+        action: RebalanceAction::DepositSplStakeToLiquid,
+        lamports,
+    });
     Ok(())
 }
 
@@ -235,7 +243,7 @@ mod tests {
         let deactivation_epoch: u64 = 10;
         let current_epoch: u64 = 10;
         assert!(
-            !(deactivation_epoch < current_epoch),
+            deactivation_epoch >= current_epoch,
             "Same epoch means still deactivating"
         );
 
@@ -243,7 +251,7 @@ mod tests {
         let deactivation_epoch: u64 = u64::MAX;
         let current_epoch: u64 = 10;
         assert!(
-            !(deactivation_epoch < current_epoch),
+            deactivation_epoch >= current_epoch,
             "MAX means not deactivated"
         );
     }
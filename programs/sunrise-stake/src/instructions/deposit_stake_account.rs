@@ -1,12 +1,14 @@
+use crate::events::DepositEvent;
 use crate::marinade::program::MarinadeFinance;
-use crate::state::State;
+use crate::state::{operation, EpochReportAccount, PoolRegistry, PoolType, State};
 use crate::utils::marinade;
-use crate::utils::seeds::{GSOL_MINT_AUTHORITY, MSOL_ACCOUNT};
+use crate::utils::seeds::{EPOCH_REPORT_ACCOUNT, GSOL_MINT_AUTHORITY, MSOL_ACCOUNT, POOL_REGISTRY};
 use crate::utils::token::mint_to;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct DepositStakeAccount<'info> {
     #[account(mut, has_one = marinade_state)]
@@ -82,6 +84,20 @@ pub struct DepositStakeAccount<'info> {
 
     /// CHECK: Checked in marinade program
     pub stake_program: AccountInfo<'info>,
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Option<Box<Account<'info, EpochReportAccount>>>,
+
+    /// Required once the registry has been created, see State::check_accepts_deposits
+    #[account(
+    seeds = [state.key().as_ref(), POOL_REGISTRY],
+    bump = pool_registry.bump,
+    )]
+    pub pool_registry: Option<Box<Account<'info, PoolRegistry>>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub marinade_program: Program<'info, MarinadeFinance>,
@@ -91,7 +107,23 @@ pub fn deposit_stake_account_handler(
     ctx: Context<DepositStakeAccount>,
     validator_index: u32,
 ) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::DEPOSIT_STAKE_ACCOUNT)?;
+    ctx.accounts.state.check_accepts_deposits(
+        ctx.accounts
+            .pool_registry
+            .as_deref()
+            .map(|registry| &**registry),
+        ctx.accounts.marinade_state.key,
+        PoolType::Marinade,
+    )?;
     let lamports = marinade::get_delegated_stake_amount(&ctx.accounts.stake_account)?;
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        lamports,
+    )?;
 
     msg!("Depositing stake account");
     marinade::deposit_stake_account(ctx.accounts, validator_index)?;
@@ -107,5 +139,13 @@ pub fn deposit_stake_account_handler(
     )?;
     let state = &mut ctx.accounts.state;
     state.marinade_minted_gsol = state.marinade_minted_gsol.checked_add(lamports).unwrap();
+
+    emit_cpi!(DepositEvent {
+        state: ctx.accounts.state.key(),
+        depositor: ctx.accounts.stake_authority.key(),
+        pool: ctx.accounts.marinade_state.key(),
+        lamports,
+        gsol_minted: lamports,
+    });
     Ok(())
 }
@@ -0,0 +1,52 @@
+use crate::error::ErrorCode;
+use crate::events::StateUpdatedEvent;
+use crate::state::{PendingStateChange, State};
+use crate::utils::seeds::PENDING_STATE_CHANGE;
+use anchor_lang::prelude::*;
+
+/// Any token accounts needed by a new marinade or blaze state are created
+/// by calling update_state after this instruction.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteStateUpdate<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = update_authority,
+        seeds = [state.key().as_ref(), PENDING_STATE_CHANGE],
+        bump = pending_state_change.bump,
+    )]
+    pub pending_state_change: Box<Account<'info, PendingStateChange>>,
+}
+
+pub fn execute_state_update_handler(ctx: Context<ExecuteStateUpdate>) -> Result<()> {
+    let pending_state_change = &ctx.accounts.pending_state_change;
+    require!(
+        pending_state_change.is_ready(Clock::get()?.unix_timestamp),
+        ErrorCode::StateUpdateNotReady
+    );
+    // The update authority may have been transferred since the change was queued
+    require_keys_eq!(
+        pending_state_change.update_authority,
+        ctx.accounts.state.update_authority,
+        ErrorCode::UpdateAuthorityChangeNotAllowed
+    );
+
+    ctx.accounts
+        .state
+        .set_timelocked_values(&pending_state_change.input);
+
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: ctx.accounts.state.update_authority,
+    });
+    Ok(())
+}
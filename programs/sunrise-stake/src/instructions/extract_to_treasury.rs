@@ -1,7 +1,10 @@
 use crate::marinade::program::MarinadeFinance;
 use crate::{
     error::ErrorCode,
-    state::{EpochReportAccount, State},
+    events::YieldExtractedEvent,
+    instructions::ExtractionMode,
+    state::{operation, EpochReportAccount, State},
+    sunrise_spl::value_pool_entries,
     utils::marinade,
     utils::marinade::CalculateExtractableYieldProperties,
     utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, MSOL_ACCOUNT},
@@ -10,6 +13,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct ExtractToTreasury<'info> {
     #[account(
@@ -104,9 +108,12 @@ pub struct ExtractToTreasury<'info> {
 }
 
 pub fn extract_to_treasury_handler(ctx: Context<ExtractToTreasury>) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::EXTRACT)?;
     // TODO at present, this withdraws all msol yield. In future, we should be able to choose how much to withdraw
     let calculate_yield_accounts: CalculateExtractableYieldProperties = ctx.accounts.deref().into();
-    let extractable_yield = marinade::calculate_extractable_yield(&calculate_yield_accounts)?;
+    let (entry_pools_value, _) = value_pool_entries(&ctx.accounts.state, ctx.remaining_accounts)?;
+    let extractable_yield =
+        marinade::calculate_extractable_yield(&calculate_yield_accounts, entry_pools_value)?;
 
     // update the epoch report with the yield that is being extracted
     ctx.accounts
@@ -124,5 +131,11 @@ pub fn extract_to_treasury_handler(ctx: Context<ExtractToTreasury>) -> Result<()
     let accounts = ctx.accounts.deref().into();
     marinade::unstake(&accounts, extractable_yield_msol)?;
 
+    emit_cpi!(YieldExtractedEvent {
+        state: ctx.accounts.state.key(),
+        recipient: ctx.accounts.treasury.key(),
+        lamports: extractable_yield,
+        mode: ExtractionMode::LiquidUnstake,
+    });
     Ok(())
 }
@@ -0,0 +1,325 @@
+use crate::marinade::program::MarinadeFinance;
+use crate::sunrise_spl::{
+    value_pool_entries, withdraw_sol_from_pool, PoolAuthoritySeeds, SplWithdrawSolProperties,
+};
+use crate::{
+    error::ErrorCode,
+    events::YieldExtractedEvent,
+    state::{operation, EpochReportAccount, State, SunriseTicketAccount, YieldRecipients},
+    utils::calc::split_proportionally,
+    utils::marinade,
+    utils::marinade::GenericUnstakeProperties,
+    utils::marinade::{CalculateExtractableYieldProperties, OrderUnstakeProperties},
+    utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, MSOL_ACCOUNT, YIELD_RECIPIENTS},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use std::ops::Deref;
+
+/// How extracted yield is realised into SOL for the treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtractionMode {
+    /// Liquid unstake mSOL through the Marinade liquidity pool (incurs the liquidity pool fee)
+    LiquidUnstake,
+    /// Order a delayed unstake of mSOL from Marinade. The SOL can be claimed to the treasury
+    /// with `claim_unstake_ticket_on_behalf` once the ticket is due.
+    DelayedUnstake,
+    /// Withdraw SOL from the Blaze pool reserve
+    BlazeWithdraw,
+    /// Split the amount between Marinade (liquid unstake) and Blaze (withdraw SOL)
+    /// in proportion to each pool's excess value over the gSOL minted against it,
+    /// so that the backing of each pool stays balanced.
+    Balanced,
+}
+
+#[event_cpi]
+#[derive(Accounts, Clone)]
+pub struct ExtractToTreasuryV2<'info> {
+    #[account(
+    has_one = treasury,
+    has_one = marinade_state,
+    has_one = blaze_state,
+    has_one = gsol_mint,
+    constraint = state.is_operator(operator.key) @ ErrorCode::InvalidOperator,
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The operator or update authority, who chooses how much yield to extract and how
+    pub operator: Signer<'info>,
+
+    /// CHECK: Validated in handler
+    #[account(mut)]
+    pub marinade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Must match state
+    #[account(mut)]
+    pub blaze_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub bsol_mint: Box<Account<'info, Mint>>,
+
+    pub liq_pool_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    mut,
+    token::mint = liq_pool_mint,
+    // use the same authority PDA for this and the msol token account
+    token::authority = get_msol_from_authority
+    )]
+    pub liq_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub treasury_msol_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    mut,
+    token::mint = msol_mint,
+    token::authority = get_msol_from_authority,
+    )]
+    pub get_msol_from: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), MSOL_ACCOUNT],
+    bump = state.msol_authority_bump
+    )]
+    pub get_msol_from_authority: SystemAccount<'info>, // sunrise-stake PDA
+
+    #[account(
+    mut,
+    token::mint = bsol_mint,
+    token::authority = get_bsol_from_authority,
+    )]
+    pub get_bsol_from: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    seeds = [state.key().as_ref(), BSOL_ACCOUNT],
+    bump = state.bsol_authority_bump
+    )]
+    pub get_bsol_from_authority: SystemAccount<'info>, // sunrise-stake PDA
+
+    #[account(mut)]
+    /// CHECK: Matches state.treasury
+    pub treasury: UncheckedAccount<'info>, // sunrise-stake treasury
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump = epoch_report_account.bump,
+    constraint = epoch_report_account.epoch == clock.epoch @ ErrorCode::InvalidEpochReportAccount
+    )]
+    pub epoch_report_account: Box<Account<'info, EpochReportAccount>>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub marinade_program: Program<'info, MarinadeFinance>,
+
+    // Required for ExtractionMode::DelayedUnstake
+    #[account(mut)]
+    /// CHECK: Checked in marinade program
+    pub new_ticket_account: Option<UncheckedAccount<'info>>,
+    #[account(init, space = SunriseTicketAccount::SPACE, payer = payer)]
+    pub sunrise_ticket_account: Option<Box<Account<'info, SunriseTicketAccount>>>,
+
+    // Required for ExtractionMode::BlazeWithdraw
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub stake_pool_withdraw_authority: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub reserve_stake_account: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub manager_fee_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub sysvar_stake_history: Option<UncheckedAccount<'info>>,
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub native_stake_program: Option<UncheckedAccount<'info>>,
+
+    // Required for extract_to_yield_recipients
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), YIELD_RECIPIENTS],
+    bump = yield_recipients.bump,
+    )]
+    pub yield_recipients: Option<Box<Account<'info, YieldRecipients>>>,
+}
+
+/// Unwrap an optional account that is required for the chosen extraction mode
+fn required<T: Clone>(account: &Option<T>) -> Result<T> {
+    account.clone().ok_or(error!(ErrorCode::MissingAccount))
+}
+
+impl<'info> ExtractToTreasuryV2<'info> {
+    fn spl_withdraw_sol_properties(
+        &self,
+        recipient: &AccountInfo<'info>,
+    ) -> Result<SplWithdrawSolProperties<'info>> {
+        Ok(SplWithdrawSolProperties {
+            authority_seeds: PoolAuthoritySeeds::new(&self.state, &None),
+            bsol_token_account: self.get_bsol_from.to_account_info(),
+            bsol_account_authority: self.get_bsol_from_authority.to_account_info(),
+            stake_pool: self.blaze_state.to_account_info(),
+            stake_pool_withdraw_authority: required(&self.stake_pool_withdraw_authority)?
+                .to_account_info(),
+            reserve_stake_account: required(&self.reserve_stake_account)?.to_account_info(),
+            recipient: recipient.clone(),
+            manager_fee_account: required(&self.manager_fee_account)?.to_account_info(),
+            stake_pool_token_mint: self.bsol_mint.to_account_info(),
+            sysvar_clock: self.clock.to_account_info(),
+            sysvar_stake_history: required(&self.sysvar_stake_history)?.to_account_info(),
+            native_stake_program: required(&self.native_stake_program)?.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+        })
+    }
+}
+
+pub fn extract_to_treasury_v2_handler(
+    ctx: Context<ExtractToTreasuryV2>,
+    amount: u64,
+    mode: ExtractionMode,
+) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::EXTRACT)?;
+    let treasury = ctx.accounts.treasury.to_account_info();
+    let (entry_pools_value, _) = value_pool_entries(&ctx.accounts.state, ctx.remaining_accounts)?;
+    let extracted = extract_yield(ctx.accounts, amount, mode, &treasury, entry_pools_value)?;
+
+    emit_cpi!(YieldExtractedEvent {
+        state: ctx.accounts.state.key(),
+        recipient: treasury.key(),
+        lamports: extracted,
+        mode,
+    });
+    Ok(())
+}
+
+/// Extract up to `amount` of yield, sending the proceeds (or the delayed unstake ticket) to `recipient`.
+/// `entry_pools_value` is the value of the holdings in pools with an entry, from value_pool_entries.
+/// Returns the amount of yield extracted.
+pub fn extract_yield<'info>(
+    accounts: &mut ExtractToTreasuryV2<'info>,
+    amount: u64,
+    mode: ExtractionMode,
+    recipient: &AccountInfo<'info>,
+    entry_pools_value: u64,
+) -> Result<u64> {
+    let calculate_yield_accounts: CalculateExtractableYieldProperties = accounts.deref().into();
+    let holdings_value =
+        marinade::calculate_holdings_value(&calculate_yield_accounts, entry_pools_value)?;
+    let extractable_yield =
+        marinade::extractable_yield_of_holdings(&holdings_value, accounts.gsol_mint.supply);
+
+    let amount = amount.min(extractable_yield);
+    msg!(
+        "Extracting {} of {} extractable yield ({:?})",
+        amount,
+        extractable_yield,
+        mode
+    );
+
+    accounts.epoch_report_account.current_gsol_supply = accounts.gsol_mint.supply;
+
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let (liquid_unstake_amount, blaze_withdraw_amount) = match mode {
+        ExtractionMode::LiquidUnstake => (amount, 0),
+        ExtractionMode::BlazeWithdraw => (0, amount),
+        ExtractionMode::Balanced => {
+            let marinade_excess = holdings_value
+                .marinade()
+                .saturating_sub(accounts.state.marinade_minted_gsol);
+            let blaze_excess = holdings_value
+                .blaze
+                .saturating_sub(accounts.state.blaze_minted_gsol);
+            msg!(
+                "(marinade excess, blaze excess) => ({}, {})",
+                marinade_excess,
+                blaze_excess
+            );
+            split_proportionally(amount, marinade_excess, blaze_excess)?
+        }
+        ExtractionMode::DelayedUnstake => {
+            let marinade_state = marinade::deserialize_marinade_state(&accounts.marinade_state)?;
+            let msol_amount = marinade::calc_msol_from_lamports(&marinade_state, amount)?;
+
+            msg!(
+                "Ordering delayed unstake of {} msol for {}",
+                msol_amount,
+                recipient.key
+            );
+            let order_unstake_accounts: OrderUnstakeProperties = accounts.deref().try_into()?;
+            marinade::order_unstake(&order_unstake_accounts, msol_amount)?;
+
+            // Recorded in the epoch report's treasury tickets rather than its tickets,
+            // which recover_tickets returns to the liquidity pool.
+            // The sunrise ticket lets claim_unstake_ticket_on_behalf pay it to the recipient.
+            let ticket_lamports =
+                marinade::calc_lamports_from_msol_amount(&marinade_state, msol_amount)?;
+            accounts
+                .epoch_report_account
+                .add_treasury_ticket(ticket_lamports, &accounts.clock)?;
+            let new_ticket_account = required(&accounts.new_ticket_account)?;
+            let sunrise_ticket_account = accounts
+                .sunrise_ticket_account
+                .as_mut()
+                .ok_or(ErrorCode::MissingAccount)?;
+            sunrise_ticket_account.state_address = accounts.state.key();
+            sunrise_ticket_account.marinade_ticket_account = new_ticket_account.key();
+            sunrise_ticket_account.beneficiary = recipient.key();
+
+            (0, 0)
+        }
+    };
+
+    // update the epoch report with the yield that is being extracted.
+    // Delayed unstakes are only counted as extracted once their epoch is over,
+    // see EpochReportAccount::settle_treasury_tickets
+    accounts.epoch_report_account.add_extracted_yield(
+        liquid_unstake_amount
+            .checked_add(blaze_withdraw_amount)
+            .unwrap(),
+    );
+
+    if liquid_unstake_amount > 0 {
+        let marinade_state = marinade::deserialize_marinade_state(&accounts.marinade_state)?;
+        let msol_amount =
+            marinade::calc_msol_from_lamports(&marinade_state, liquid_unstake_amount)?;
+
+        msg!("Liquid unstaking {} msol to {}", msol_amount, recipient.key);
+        let mut unstake_accounts: GenericUnstakeProperties = accounts.deref().into();
+        unstake_accounts.recipient = recipient.clone();
+        marinade::unstake(&unstake_accounts, msol_amount)?;
+    }
+
+    if blaze_withdraw_amount > 0 {
+        msg!(
+            "Withdrawing {} lamports from blaze to {}",
+            blaze_withdraw_amount,
+            recipient.key
+        );
+        let withdraw_accounts = accounts.spl_withdraw_sol_properties(recipient)?;
+        withdraw_sol_from_pool(&withdraw_accounts, blaze_withdraw_amount)?;
+    }
+
+    Ok(amount)
+}
@@ -0,0 +1,119 @@
+use crate::events::YieldExtractedEvent;
+use crate::instructions::{extract_yield, ExtractToTreasuryV2, ExtractionMode};
+use crate::state::operation;
+use crate::sunrise_spl::value_pool_entries;
+use crate::utils::calc::{proportional, MAX_BASIS_POINTS};
+use crate::utils::system;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+/// Extract yield and split it between the yield recipients according to their weights.
+/// The recipients must be passed in the remaining accounts, in the same order as
+/// in the YieldRecipients account, followed by the pool entries (see value_pool_entries).
+/// The yield is first extracted to the msol authority PDA, and the amount received
+/// is then distributed, so any unstake fees are borne by all recipients equally.
+/// A share too small to open an empty recipient account is carried forward to the next extraction.
+pub fn extract_to_yield_recipients_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExtractToTreasuryV2<'info>>,
+    amount: u64,
+    mode: ExtractionMode,
+) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::EXTRACT)?;
+    // A delayed unstake ticket has a single beneficiary, so it cannot be split
+    require!(
+        mode != ExtractionMode::DelayedUnstake,
+        ErrorCode::UnsupportedExtractionMode
+    );
+
+    let (entry_pools_value, recipient_accounts) =
+        value_pool_entries(&ctx.accounts.state, ctx.remaining_accounts)?;
+    let mut yield_recipients = ctx
+        .accounts
+        .yield_recipients
+        .clone()
+        .ok_or(ErrorCode::MissingAccount)?;
+    require_eq!(
+        yield_recipients.recipients.len(),
+        recipient_accounts.len(),
+        ErrorCode::UnexpectedAccounts
+    );
+    for (recipient, account) in yield_recipients
+        .recipients
+        .iter()
+        .zip(recipient_accounts.iter())
+    {
+        require_keys_eq!(
+            recipient.recipient,
+            account.key(),
+            ErrorCode::UnexpectedAccounts
+        );
+    }
+
+    let msol_authority = ctx.accounts.get_msol_from_authority.to_account_info();
+    let lamports_before = msol_authority.lamports();
+    extract_yield(
+        ctx.accounts,
+        amount,
+        mode,
+        &msol_authority,
+        entry_pools_value,
+    )?;
+    let received = msol_authority
+        .lamports()
+        .checked_sub(lamports_before)
+        .ok_or(ErrorCode::InvalidCalculation)?;
+
+    let to_distribute = received
+        .checked_add(yield_recipients.carried_lamports)
+        .ok_or(ErrorCode::CalculationFailure)?;
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+
+    // The last recipient receives any rounding remainder
+    let mut remaining = to_distribute;
+    let mut carried = 0;
+    let last_index = yield_recipients.recipients.len().saturating_sub(1);
+    for (index, (recipient, account)) in yield_recipients
+        .recipients
+        .iter()
+        .zip(recipient_accounts.iter())
+        .enumerate()
+    {
+        let share = if index == last_index {
+            remaining
+        } else {
+            proportional(to_distribute, recipient.weight_bps as u64, MAX_BASIS_POINTS)?
+        };
+        remaining = remaining.checked_sub(share).unwrap();
+
+        // Transferring less than the rent-exempt minimum to an empty account fails
+        let share = if account.lamports() == 0 && share < rent_exempt_minimum {
+            msg!("Carrying {} lamports for {} forward", share, account.key);
+            carried += share;
+            0
+        } else {
+            share
+        };
+
+        if share > 0 {
+            msg!("Sending {} lamports to {}", share, account.key);
+            system::transfer_from_msol_authority(
+                &ctx.accounts.state,
+                &msol_authority,
+                account,
+                share,
+            )?;
+        }
+
+        emit_cpi!(YieldExtractedEvent {
+            state: ctx.accounts.state.key(),
+            recipient: account.key(),
+            lamports: share,
+            mode,
+        });
+    }
+
+    yield_recipients.carried_lamports = carried;
+    ctx.accounts.yield_recipients = Some(yield_recipients);
+
+    Ok(())
+}
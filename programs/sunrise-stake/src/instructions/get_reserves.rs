@@ -0,0 +1,141 @@
+use crate::adapters::MarinadeAdapter;
+use crate::error::ErrorCode;
+use crate::state::{EpochReportAccount, State};
+use crate::sunrise_spl::value_pool_entries;
+use crate::utils::marinade::{self, CalculateExtractableYieldProperties};
+use crate::utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, MSOL_ACCOUNT};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use std::ops::Deref;
+
+/// What backs gSOL, in lamports unless stated otherwise
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct Reserves {
+    /// The sol value of this instance's share of the marinade liquidity pool
+    pub lp_value: u64,
+    /// The sol value of the msol held
+    pub msol_value: u64,
+    /// The sol value of the bsol held, net of withdrawal fees
+    pub bsol_value: u64,
+    /// Lamports ordered for delayed unstake this epoch and not yet recovered
+    pub delayed_unstake_lamports: u64,
+    /// Lamports ordered for delayed unstake this epoch on behalf of the treasury.
+    /// These are extracted yield, so do not back gSOL.
+    pub treasury_delayed_unstake_lamports: u64,
+    pub gsol_supply: u64,
+    pub marinade_minted_gsol: u64,
+    pub blaze_minted_gsol: u64,
+    pub extractable_yield: u64,
+    /// The sol value of the pool tokens held in pools with an entry, net of withdrawal fees
+    pub entry_pools_value: u64,
+}
+
+#[derive(Accounts, Clone)]
+pub struct GetReserves<'info> {
+    #[account(
+    has_one = gsol_mint,
+    has_one = marinade_state,
+    has_one = blaze_state,
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: Validated in handler using deserialize_marinade_state()
+    pub marinade_state: UncheckedAccount<'info>,
+
+    /// CHECK: Must match state, validated in handler using deserialize_spl_stake_pool()
+    pub blaze_state: UncheckedAccount<'info>,
+
+    pub msol_mint: Box<Account<'info, Mint>>,
+
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    pub bsol_mint: Box<Account<'info, Mint>>,
+
+    pub liq_pool_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Checked against the marinade state in the handler
+    pub liq_pool_sol_leg_pda: UncheckedAccount<'info>,
+
+    pub liq_pool_msol_leg: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    token::mint = liq_pool_mint,
+    // use the same authority PDA for this and the msol token account
+    token::authority = get_msol_from_authority
+    )]
+    pub liq_pool_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    token::mint = msol_mint,
+    token::authority = get_msol_from_authority,
+    )]
+    pub get_msol_from: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    seeds = [state.key().as_ref(), MSOL_ACCOUNT],
+    bump = state.msol_authority_bump
+    )]
+    pub get_msol_from_authority: SystemAccount<'info>, // sunrise-stake PDA
+
+    #[account(
+    token::mint = bsol_mint,
+    token::authority = get_bsol_from_authority,
+    )]
+    pub get_bsol_from: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+    seeds = [state.key().as_ref(), BSOL_ACCOUNT],
+    bump = state.bsol_authority_bump
+    )]
+    pub get_bsol_from_authority: SystemAccount<'info>, // sunrise-stake PDA
+
+    #[account(
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Box<Account<'info, EpochReportAccount>>,
+}
+
+pub fn get_reserves_handler(ctx: Context<GetReserves>) -> Result<Reserves> {
+    let accounts = ctx.accounts;
+    let marinade = MarinadeAdapter::load(&accounts.marinade_state)?;
+    let marinade_state = &marinade.marinade_state;
+
+    // Nothing here is checked by a CPI to marinade, so check the accounts we value
+    require_keys_eq!(
+        accounts.msol_mint.key(),
+        marinade_state.msol_mint,
+        ErrorCode::InvalidMarinadeAccount
+    );
+    marinade::check_liq_pool_accounts(
+        marinade_state,
+        accounts.marinade_state.key,
+        &accounts.liq_pool_mint.key(),
+        accounts.liq_pool_sol_leg_pda.key,
+        &accounts.liq_pool_msol_leg.key(),
+    )?;
+
+    // calculate_holdings_value checks the bsol mint against the blaze pool
+    let calculate_yield_accounts: CalculateExtractableYieldProperties = accounts.deref().into();
+    let (entry_pools_value, _) = value_pool_entries(&accounts.state, ctx.remaining_accounts)?;
+    let holdings_value =
+        marinade::calculate_holdings_value(&calculate_yield_accounts, entry_pools_value)?;
+    let gsol_supply = accounts.gsol_mint.supply;
+    let extractable_yield = marinade::extractable_yield_of_holdings(&holdings_value, gsol_supply);
+
+    // Tickets from an earlier epoch are still in flight until recovered
+    let epoch_report = &accounts.epoch_report_account;
+
+    Ok(Reserves {
+        lp_value: holdings_value.lp,
+        msol_value: holdings_value.msol,
+        bsol_value: holdings_value.blaze,
+        delayed_unstake_lamports: epoch_report.total_ordered_lamports,
+        treasury_delayed_unstake_lamports: epoch_report.treasury_ordered_lamports,
+        gsol_supply,
+        marinade_minted_gsol: accounts.state.marinade_minted_gsol,
+        blaze_minted_gsol: accounts.state.blaze_minted_gsol,
+        extractable_yield,
+        entry_pools_value,
+    })
+}
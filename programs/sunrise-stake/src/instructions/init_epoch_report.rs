@@ -1,4 +1,6 @@
+use crate::events::EpochReportUpdatedEvent;
 use crate::state::{EpochReportAccount, State};
+use crate::sunrise_spl::value_pool_entries;
 use crate::utils::marinade;
 use crate::utils::marinade::CalculateExtractableYieldProperties;
 use crate::utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, MSOL_ACCOUNT};
@@ -6,6 +8,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct InitEpochReport<'info> {
     #[account(
@@ -109,11 +112,17 @@ pub fn init_epoch_report_handler<'info>(
     ctx.accounts.epoch_report_account.epoch = ctx.accounts.clock.epoch;
     ctx.accounts.epoch_report_account.tickets = 0;
     ctx.accounts.epoch_report_account.total_ordered_lamports = 0;
+    ctx.accounts.epoch_report_account.treasury_tickets = 0;
+    ctx.accounts.epoch_report_account.treasury_ordered_lamports = 0;
+    ctx.accounts.epoch_report_account.deposit_epoch = ctx.accounts.clock.epoch;
+    ctx.accounts.epoch_report_account.deposited_lamports = 0;
     ctx.accounts.epoch_report_account.current_gsol_supply = ctx.accounts.gsol_mint.supply;
     ctx.accounts.epoch_report_account.bump = ctx.bumps.epoch_report_account;
 
     let calculate_yield_accounts: CalculateExtractableYieldProperties = ctx.accounts.deref().into();
-    let extractable_yield = marinade::calculate_extractable_yield(&calculate_yield_accounts)?;
+    let (entry_pools_value, _) = value_pool_entries(&ctx.accounts.state, ctx.remaining_accounts)?;
+    let extractable_yield =
+        marinade::calculate_extractable_yield(&calculate_yield_accounts, entry_pools_value)?;
 
     ctx.accounts.epoch_report_account.extractable_yield = extractable_yield;
 
@@ -122,5 +131,8 @@ pub fn init_epoch_report_handler<'info>(
     // This is why this instruction is only callable by the update authority
     ctx.accounts.epoch_report_account.extracted_yield = extracted_yield;
 
+    emit_cpi!(EpochReportUpdatedEvent::from(
+        &**ctx.accounts.epoch_report_account
+    ));
     Ok(())
 }
@@ -0,0 +1,47 @@
+use crate::events::EpochReportHistoryCreatedEvent;
+use crate::state::{EpochReportAccount, EpochReportHistory, State};
+use crate::utils::seeds::{EPOCH_REPORT_ACCOUNT, EPOCH_REPORT_HISTORY};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitEpochReportHistory<'info> {
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Box<Account<'info, EpochReportAccount>>,
+
+    #[account(
+    init,
+    space = EpochReportHistory::SPACE,
+    payer = payer,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_HISTORY],
+    bump,
+    )]
+    pub epoch_report_history: Box<Account<'info, EpochReportHistory>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_epoch_report_history_handler(ctx: Context<InitEpochReportHistory>) -> Result<()> {
+    let history = &mut ctx.accounts.epoch_report_history;
+    history.state_address = ctx.accounts.state.key();
+    history.next_index = 0;
+    history.entries = vec![];
+    history.bump = ctx.bumps.epoch_report_history;
+
+    // start the history with the current report
+    history.record((&**ctx.accounts.epoch_report_account).into());
+
+    emit_cpi!(EpochReportHistoryCreatedEvent {
+        state: ctx.accounts.state.key(),
+        epoch_report_history: ctx.accounts.epoch_report_history.key(),
+    });
+    Ok(())
+}
@@ -1,8 +1,10 @@
+use crate::events::{LockAction, LockEvent};
 use crate::state::{LockAccount, State};
 use crate::utils::seeds::{LOCK_ACCOUNT, LOCK_TOKEN_ACCOUNT};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct InitLockAccount<'info> {
     #[account(
@@ -50,5 +52,11 @@ pub fn init_lock_account_handler(ctx: Context<InitLockAccount>) -> Result<()> {
     ctx.accounts.lock_account.bump = ctx.bumps.lock_account;
     ctx.accounts.lock_account.sunrise_yield_at_start = 0;
     ctx.accounts.lock_account.yield_accrued_by_owner = 0;
+
+    emit_cpi!(LockEvent::new(
+        &ctx.accounts.lock_account,
+        LockAction::Init,
+        0
+    ));
     Ok(())
 }
@@ -0,0 +1,87 @@
+use crate::adapters::SplStakePoolAdapter;
+use crate::events::{PoolAction, PoolEvent};
+use crate::state::{PoolEntry, PoolRegistry, PoolType, State};
+use crate::utils::seeds::{POOL_AUTHORITY, POOL_ENTRY, POOL_REGISTRY};
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitPoolEntry<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        seeds = [state.key().as_ref(), POOL_REGISTRY],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
+    /// CHECK: Must be registered as an SPL pool, and is deserialized in the handler
+    pub stake_pool: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        space = PoolEntry::SPACE,
+        payer = payer,
+        seeds = [state.key().as_ref(), POOL_ENTRY, stake_pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_entry: Box<Account<'info, PoolEntry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_pool_entry_handler(ctx: Context<InitPoolEntry>) -> Result<()> {
+    let state_key = ctx.accounts.state.key();
+    let pool = ctx.accounts.stake_pool.key();
+    require_keys_neq!(
+        pool,
+        ctx.accounts.state.blaze_state,
+        ErrorCode::BlazePoolHasNoEntry
+    );
+    let registered = ctx
+        .accounts
+        .pool_registry
+        .find(&pool)
+        .ok_or(ErrorCode::PoolNotRegistered)?;
+    require!(
+        registered.pool_type == PoolType::Spl,
+        ErrorCode::InvalidPoolType
+    );
+
+    let adapter = SplStakePoolAdapter::load(&ctx.accounts.stake_pool)?;
+    let (_, authority_bump) = Pubkey::find_program_address(
+        &[state_key.as_ref(), POOL_AUTHORITY, pool.as_ref()],
+        ctx.program_id,
+    );
+
+    let pool_entry = &mut ctx.accounts.pool_entry;
+    pool_entry.state_address = state_key;
+    pool_entry.pool = pool;
+    pool_entry.pool_mint = adapter.stake_pool.pool_mint;
+    pool_entry.authority_bump = authority_bump;
+    pool_entry.minted_gsol = 0;
+    pool_entry.bump = ctx.bumps.pool_entry;
+
+    let state = &mut ctx.accounts.state;
+    state.pool_entries = state
+        .pool_entries
+        .checked_add(1)
+        .ok_or(ErrorCode::CalculationFailure)?;
+
+    emit_cpi!(PoolEvent {
+        state: state_key,
+        pool,
+        action: PoolAction::InitEntry,
+    });
+    Ok(())
+}
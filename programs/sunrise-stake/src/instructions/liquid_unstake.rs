@@ -1,5 +1,8 @@
+use crate::adapters::{SplStakePoolAdapter, StakePoolAdapter};
+use crate::error::ErrorCode;
+use crate::events::LiquidUnstakeEvent;
 use crate::marinade::program::MarinadeFinance;
-use crate::state::State;
+use crate::state::{operation, State};
 use crate::utils::seeds::{BSOL_ACCOUNT, GSOL_MINT_AUTHORITY, MSOL_ACCOUNT};
 use crate::utils::token::burn;
 use crate::utils::{marinade, spl};
@@ -8,6 +11,7 @@ use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 #[instruction(lamports: u64)]
 pub struct LiquidUnstake<'info> {
@@ -131,7 +135,36 @@ pub struct LiquidUnstake<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
+/// Liquid unstake, failing if the user receives fewer than `min_lamports_out` lamports.
+/// The amount received is measured as the change in the user's SOL balance across all CPIs,
+/// so it includes any Marinade or Blaze fees.
+pub fn liquid_unstake_v2_handler(
+    ctx: Context<LiquidUnstake>,
+    lamports: u64,
+    min_lamports_out: u64,
+) -> Result<()> {
+    let user = ctx.accounts.gsol_token_account_authority.to_account_info();
+    let lamports_before = user.lamports();
+
+    liquid_unstake_handler(ctx, lamports)?;
+
+    let lamports_out = user
+        .lamports()
+        .checked_sub(lamports_before)
+        .ok_or(ErrorCode::MinLamportsOutNotMet)?;
+    require_gte!(
+        lamports_out,
+        min_lamports_out,
+        ErrorCode::MinLamportsOutNotMet
+    );
+
+    Ok(())
+}
+
 pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::LIQUID_UNSTAKE)?;
     msg!("Checking liq_pool pool balance");
     let calculate_balance_props = ctx.accounts.deref().into();
     let amounts = marinade::calculate_pool_balance_amounts(&calculate_balance_props, lamports)?;
@@ -150,9 +183,13 @@ pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Res
     )?;
     msg!("msol account valuation: {}", msol_account_valuation);
 
-    let blaze_pool = spl::deserialize_spl_stake_pool(&ctx.accounts.blaze_stake_pool)?;
-    let bsol_account_valuation =
-        spl::calc_lamports_from_bsol_amount(&blaze_pool, ctx.accounts.bsol_token_account.amount)?;
+    let blaze = SplStakePoolAdapter::load(&ctx.accounts.blaze_stake_pool)?;
+    require_keys_eq!(
+        ctx.accounts.bsol_token_account.mint,
+        blaze.stake_pool.pool_mint,
+        ErrorCode::InvalidStakePoolAccount
+    );
+    let bsol_account_valuation = blaze.value_of_holdings(ctx.accounts.bsol_token_account.amount)?;
     msg!("bsol account valuation: {}", bsol_account_valuation);
 
     let liquid_unstake_amount = amounts.amount_to_liquid_unstake;
@@ -176,6 +213,14 @@ pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Res
         // which would block users from unstaking.
         let state = &mut ctx.accounts.state;
         state.marinade_minted_gsol = state.marinade_minted_gsol.saturating_sub(lamports);
+
+        emit_cpi!(LiquidUnstakeEvent {
+            state: ctx.accounts.state.key(),
+            staker: ctx.accounts.gsol_token_account_authority.key(),
+            gsol_burned: lamports,
+            marinade_lamports: lamports,
+            blaze_lamports: 0,
+        });
         return Ok(());
     }
 
@@ -223,7 +268,11 @@ pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Res
     }
 
     if blaze_withdraw_amount > 0 {
-        let bsol_value = spl::calc_bsol_from_lamports(&blaze_pool, blaze_withdraw_amount)?;
+        // Withdrawing from Blaze is an SPL withdrawal, so honour its pause flag too
+        ctx.accounts
+            .state
+            .check_not_paused(operation::SPL_WITHDRAW)?;
+        let bsol_value = spl::calc_bsol_from_lamports(&blaze.stake_pool, blaze_withdraw_amount)?;
         msg!(
             "Unstaking {} lamports({} bsol) from blaze",
             blaze_withdraw_amount,
@@ -237,6 +286,7 @@ pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Res
             bsol_token_account: ctx.accounts.bsol_token_account.clone(),
             bsol_account_authority: ctx.accounts.bsol_account_authority.clone(),
             stake_pool: ctx.accounts.blaze_stake_pool.clone(),
+            pool_entry: None,
             stake_pool_withdraw_authority: ctx.accounts.stake_pool_withdraw_authority.clone(),
             reserve_stake_account: ctx.accounts.reserve_stake_account.clone(),
             manager_fee_account: ctx.accounts.manager_fee_account.clone(),
@@ -246,6 +296,8 @@ pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Res
             stake_pool_program: ctx.accounts.stake_pool_program.clone(),
             native_stake_program: ctx.accounts.native_stake_program.clone(),
             token_program: ctx.accounts.token_program.clone(),
+            event_authority: ctx.accounts.event_authority.clone(),
+            program: ctx.accounts.program.clone(),
         };
         accounts.withdraw_sol(blaze_withdraw_amount)?;
     }
@@ -257,5 +309,12 @@ pub fn liquid_unstake_handler(ctx: Context<LiquidUnstake>, lamports: u64) -> Res
     let marinade_portion = lamports.saturating_sub(blaze_withdraw_amount);
     state.marinade_minted_gsol = state.marinade_minted_gsol.saturating_sub(marinade_portion);
 
+    emit_cpi!(LiquidUnstakeEvent {
+        state: ctx.accounts.state.key(),
+        staker: ctx.accounts.gsol_token_account_authority.key(),
+        gsol_burned: lamports,
+        marinade_lamports: marinade_portion,
+        blaze_lamports: blaze_withdraw_amount,
+    });
     Ok(())
 }
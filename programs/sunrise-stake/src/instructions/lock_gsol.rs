@@ -1,9 +1,10 @@
 use crate::error::ErrorCode;
+use crate::events::{LockAction, LockEvent};
 use crate::impact_nft::accounts::GlobalState as ImpactNftState;
 use crate::impact_nft::cpi::accounts::MintNft;
 use crate::impact_nft::cpi::mint_nft as cpi_mint_nft;
 use crate::impact_nft::program::ImpactNft;
-use crate::state::{EpochReportAccount, LockAccount, State};
+use crate::state::{operation, EpochReportAccount, LockAccount, State};
 use crate::utils::seeds::{
     EPOCH_REPORT_ACCOUNT, IMPACT_NFT_MINT_ACCOUNT, IMPACT_NFT_MINT_AUTHORITY, LOCK_TOKEN_ACCOUNT,
 };
@@ -12,6 +13,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 #[instruction(lamports: u64)]
 pub struct LockGSol<'info> {
@@ -120,6 +122,7 @@ pub struct LockGSol<'info> {
 }
 
 pub fn lock_gsol_handler(ctx: Context<LockGSol>, lamports: u64) -> Result<()> {
+    ctx.accounts.state.check_not_paused(operation::LOCK)?;
     transfer_to(
         lamports,
         &ctx.accounts.authority.to_account_info(),
@@ -185,5 +188,10 @@ pub fn lock_gsol_handler(ctx: Context<LockGSol>, lamports: u64) -> Result<()> {
         msg!("NFT already minted");
     }
 
+    emit_cpi!(LockEvent::new(
+        &ctx.accounts.lock_account,
+        LockAction::Lock,
+        lamports
+    ));
     Ok(())
 }
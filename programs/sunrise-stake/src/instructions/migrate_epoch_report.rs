@@ -0,0 +1,54 @@
+use crate::events::EpochReportMigratedEvent;
+use crate::state::{EpochReportAccount, State};
+use crate::utils::seeds::EPOCH_REPORT_ACCOUNT;
+use crate::utils::system;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateEpochReport<'info> {
+    #[account(has_one = update_authority)]
+    pub state: Box<Account<'info, State>>,
+
+    /// CHECK: Deserialized in the handler once resized, as an account created before
+    /// fields were added is too short to be deserialized as EpochReportAccount
+    #[account(
+    mut,
+    owner = crate::ID,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_ACCOUNT],
+    bump,
+    )]
+    pub epoch_report_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub update_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_epoch_report_handler(ctx: Context<MigrateEpochReport>) -> Result<()> {
+    let report_info = ctx.accounts.epoch_report_account.to_account_info();
+    msg!(
+        "Resizing epoch report from {} to {} bytes",
+        report_info.data_len(),
+        EpochReportAccount::SPACE
+    );
+    system::grow_account(
+        &report_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        EpochReportAccount::SPACE,
+    )?;
+
+    // Fields are only ever added to the end of the report, and start at zero
+    EpochReportAccount::try_deserialize(&mut &report_info.try_borrow_data()?[..])?;
+
+    emit_cpi!(EpochReportMigratedEvent {
+        state: ctx.accounts.state.key(),
+        epoch_report_account: report_info.key(),
+        space: report_info.data_len() as u64,
+    });
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::events::StateMigratedEvent;
+use crate::state::State;
+use crate::utils::system;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// CHECK: Deserialized in the handler, as an account that has not yet been migrated
+    /// cannot be deserialized as State
+    #[account(mut, owner = crate::ID)]
+    pub state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub update_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_state_handler(ctx: Context<MigrateState>) -> Result<()> {
+    let state_info = ctx.accounts.state.to_account_info();
+    let mut state = State::try_deserialize_any_version(&state_info.try_borrow_data()?)?;
+    require_keys_eq!(
+        state.update_authority,
+        ctx.accounts.update_authority.key(),
+        anchor_lang::error::ErrorCode::ConstraintHasOne
+    );
+
+    msg!(
+        "Migrating state from version {} to {}",
+        state.version,
+        State::CURRENT_VERSION
+    );
+    let from_version = state.version;
+    state.migrate();
+
+    system::grow_account(
+        &state_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        State::SPACE,
+    )?;
+
+    let mut data = state_info.try_borrow_mut_data()?;
+    state.try_serialize(&mut &mut data[..])?;
+
+    emit_cpi!(StateMigratedEvent {
+        state: state_info.key(),
+        from_version,
+        to_version: state.version,
+    });
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::error::ErrorCode;
+use crate::events::{RebalanceAction, RebalanceEvent};
 use crate::marinade::program::MarinadeFinance;
 use crate::state::State;
 use crate::utils::{marinade, seeds, spl};
@@ -15,18 +17,20 @@ const SPL_STAKE_POOL_ID: Pubkey =
     anchor_lang::solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
 
 /// Move SOL from SPL stake pool (liquid reserve) directly to Marinade liquidity pool.
-/// This is an admin-only instruction for rebalancing funds between pools.
+/// This is an operator-only instruction for rebalancing funds between pools.
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct MoveSplLiquidToMarinade<'info> {
     #[account(
         mut,
-        has_one = update_authority,
+        constraint = state.is_operator(operator.key) @ ErrorCode::InvalidOperator,
         has_one = marinade_state,
         constraint = state.blaze_state == *stake_pool.key
     )]
     pub state: Box<Account<'info, State>>,
 
-    pub update_authority: Signer<'info>,
+    /// The operator or update authority
+    pub operator: Signer<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -194,5 +198,10 @@ pub fn move_spl_liquid_to_marinade_handler(
         state.marinade_minted_gsol
     );
 
+    emit_cpi!(RebalanceEvent {
+        state: ctx.accounts.state.key(),
+        action: RebalanceAction::MoveSplLiquidToMarinade,
+        lamports,
+    });
     Ok(())
 }
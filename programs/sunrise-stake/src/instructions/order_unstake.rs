@@ -1,6 +1,7 @@
+use crate::events::OrderUnstakeEvent;
 use crate::marinade::program::MarinadeFinance;
-use crate::state::State;
 use crate::state::SunriseTicketAccount;
+use crate::state::{operation, State};
 use crate::utils::marinade;
 use crate::utils::marinade::{calc_lamports_from_msol_amount, calc_msol_from_lamports};
 use crate::utils::seeds::{GSOL_MINT_AUTHORITY, MSOL_ACCOUNT};
@@ -10,6 +11,7 @@ use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct OrderUnstake<'info> {
     #[account(
@@ -81,6 +83,9 @@ pub struct OrderUnstake<'info> {
 }
 
 pub fn order_unstake_handler(ctx: Context<OrderUnstake>, lamports: u64) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::ORDER_UNSTAKE)?;
     let marinade_state = marinade::deserialize_marinade_state(&ctx.accounts.marinade_state)?;
     let msol_lamports = calc_msol_from_lamports(&marinade_state, lamports)?;
 
@@ -115,5 +120,12 @@ pub fn order_unstake_handler(ctx: Context<OrderUnstake>, lamports: u64) -> Resul
     let state = &mut ctx.accounts.state;
     state.marinade_minted_gsol = state.marinade_minted_gsol.checked_sub(lamports).unwrap();
 
+    emit_cpi!(OrderUnstakeEvent {
+        state: ctx.accounts.state.key(),
+        staker: ctx.accounts.gsol_token_account_authority.key(),
+        ticket: ctx.accounts.sunrise_ticket_account.key(),
+        gsol_burned: lamports,
+        lamports: lamports_converted,
+    });
     Ok(())
 }
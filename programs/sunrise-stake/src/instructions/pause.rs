@@ -0,0 +1,36 @@
+use crate::error::ErrorCode;
+use crate::events::PauseEvent;
+use crate::state::{operation, State};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(
+        mut,
+        constraint = state.is_pause_guardian(pause_guardian.key) @ ErrorCode::InvalidPauseGuardian,
+    )]
+    pub state: Account<'info, State>,
+
+    /// The pause guardian or update authority
+    pub pause_guardian: Signer<'info>,
+}
+
+/// Pause the given operations, in addition to any already paused
+pub fn pause_handler(ctx: Context<Pause>, operations: u16) -> Result<()> {
+    require!(
+        operations & !operation::ALL == 0,
+        ErrorCode::InvalidOperationFlags
+    );
+
+    let state = &mut ctx.accounts.state;
+    state.paused_operations |= operations;
+    msg!("Paused operations: {:#06x}", state.paused_operations);
+
+    emit_cpi!(PauseEvent {
+        state: ctx.accounts.state.key(),
+        authority: ctx.accounts.pause_guardian.key(),
+        paused_operations: ctx.accounts.state.paused_operations,
+    });
+    Ok(())
+}
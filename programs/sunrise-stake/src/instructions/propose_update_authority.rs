@@ -0,0 +1,30 @@
+use crate::events::StateUpdatedEvent;
+use crate::state::State;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeUpdateAuthority<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+
+    pub update_authority: Signer<'info>,
+}
+
+pub fn propose_update_authority_handler(
+    ctx: Context<ProposeUpdateAuthority>,
+    new_update_authority: Pubkey,
+) -> Result<()> {
+    ctx.accounts
+        .state
+        .propose_update_authority(new_update_authority);
+
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: ctx.accounts.state.update_authority,
+    });
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::error::ErrorCode;
+use crate::events::StateUpdateQueuedEvent;
+use crate::state::{PendingStateChange, State, StateInput, TimelockedStateInput};
+use crate::utils::seeds::PENDING_STATE_CHANGE;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QueueStateUpdate<'info> {
+    #[account(
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub update_authority: Signer<'info>,
+
+    /// Only one change can be queued at a time. Cancel it to queue a different one.
+    #[account(
+        init,
+        space = PendingStateChange::SPACE,
+        payer = payer,
+        seeds = [state.key().as_ref(), PENDING_STATE_CHANGE],
+        bump,
+    )]
+    pub pending_state_change: Box<Account<'info, PendingStateChange>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a change to the timelocked fields of the state, which can be executed once `eta`
+/// (a unix timestamp) has passed. `eta` must be at least `state.state_update_delay` seconds from now.
+pub fn queue_state_update_handler(
+    ctx: Context<QueueStateUpdate>,
+    state: StateInput,
+    eta: i64,
+) -> Result<()> {
+    // Check the liq_pool_proportion does not exceed 100%
+    require_gte!(100, state.liq_pool_proportion);
+    state.validate()?;
+    require_keys_eq!(
+        state.update_authority,
+        ctx.accounts.state.update_authority,
+        ErrorCode::UpdateAuthorityChangeNotAllowed
+    );
+    require!(
+        !state.sets_untimelocked_fields(),
+        ErrorCode::StateUpdateNotTimelocked
+    );
+
+    let earliest_eta = Clock::get()?
+        .unix_timestamp
+        .checked_add(ctx.accounts.state.state_update_delay)
+        .ok_or(ErrorCode::CalculationFailure)?;
+    require_gte!(eta, earliest_eta, ErrorCode::StateUpdateEtaTooEarly);
+
+    let pending_state_change = &mut ctx.accounts.pending_state_change;
+    pending_state_change.state_address = ctx.accounts.state.key();
+    pending_state_change.update_authority = ctx.accounts.state.update_authority;
+    pending_state_change.input = TimelockedStateInput::new(&state, &ctx.accounts.state);
+    pending_state_change.eta = eta;
+    pending_state_change.bump = ctx.bumps.pending_state_change;
+
+    emit_cpi!(StateUpdateQueuedEvent {
+        state: ctx.accounts.state.key(),
+        pending_state_change: ctx.accounts.pending_state_change.key(),
+        eta,
+    });
+    Ok(())
+}
@@ -1,18 +1,16 @@
+use crate::events::{RebalanceAction, RebalanceEvent};
 use crate::marinade::program::MarinadeFinance;
-use crate::state::{EpochReportAccount, State, TicketAccountData};
+use crate::state::{EpochReportAccount, EpochReportHistory, State, TicketAccountData};
+use crate::sunrise_spl::value_pool_entries;
 use crate::utils::marinade;
 use crate::utils::marinade::{CalculateExtractableYieldProperties, ClaimUnstakeTicketProperties};
-use crate::utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, MSOL_ACCOUNT};
+use crate::utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, EPOCH_REPORT_HISTORY, MSOL_ACCOUNT};
 use crate::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
-// TODO: RECOVERED_MARGIN is needed because, for some reason, the claim tickets have a couple of lamports less than they should,
-// probably due to a rounding error converting to and from marinade
-// Figure this out and then remove this margin
-const RECOVERED_MARGIN: u64 = 10;
-
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct RecoverTickets<'info> {
     #[account(
@@ -110,6 +108,13 @@ pub struct RecoverTickets<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub marinade_program: Program<'info, MarinadeFinance>,
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_HISTORY],
+    bump = epoch_report_history.bump,
+    )]
+    pub epoch_report_history: Option<Box<Account<'info, EpochReportHistory>>>,
 }
 
 pub fn recover_tickets_handler<'info>(
@@ -122,18 +127,22 @@ pub fn recover_tickets_handler<'info>(
         ErrorCode::DelayedUnstakeTicketsNotYetClaimable
     );
 
+    // The remaining accounts are the tickets, followed by the pool entries
+    let (entry_pools_value, tickets) =
+        value_pool_entries(&ctx.accounts.state, ctx.remaining_accounts)?;
+
     // if the remaining accounts exceeds the amount of tickets, then fail early
     require_gte!(
         ctx.accounts.epoch_report_account.tickets,
-        ctx.remaining_accounts.len() as u64,
+        tickets.len() as u64,
         ErrorCode::TooManyTicketsClaimed
     );
 
     let mut claimed_lamports = 0;
     let mut props: ClaimUnstakeTicketProperties = ctx.accounts.deref().into();
-    // All remaining accounts are previous epoch tickets that are now ready to be claimed.
-    msg!("Tickets to claim: {}", ctx.remaining_accounts.len());
-    for ticket in ctx.remaining_accounts.iter() {
+    // All tickets are previous epoch tickets that are now ready to be claimed.
+    msg!("Tickets to claim: {}", tickets.len());
+    for ticket in tickets.iter() {
         let ticket_account = TicketAccountData::try_from_slice(&ticket.data.borrow_mut())?;
         claimed_lamports += ticket_account.lamports_amount;
 
@@ -172,7 +181,7 @@ pub fn recover_tickets_handler<'info>(
     );
 
     // If we have claimed all that is remaining to claim, and there are no more tickets, update the epoch report (we are done for this epoch)
-    // TODO: RECOVERED_MARGIN is needed because, for some reason, the claim tickets have a couple of lamports less than they should,
+    // TODO: state.recovered_margin is needed because, for some reason, the claim tickets have a couple of lamports less than they should,
     // probably due to a rounding error converting to and from marinade
     // Figure this out and then remove this margin
     if claimed_lamports
@@ -180,28 +189,40 @@ pub fn recover_tickets_handler<'info>(
             .accounts
             .epoch_report_account
             .total_ordered_lamports
-            .saturating_sub(RECOVERED_MARGIN)
+            .saturating_sub(ctx.accounts.state.recovered_margin)
     {
         msg!("Claimed total amount");
-        msg!("RemainingAccounts {}", ctx.remaining_accounts.len());
-        if ctx.remaining_accounts.len() as u64 == ctx.accounts.epoch_report_account.tickets {
+        msg!("Tickets {}", tickets.len());
+        if tickets.len() as u64 == ctx.accounts.epoch_report_account.tickets {
             ctx.accounts.liq_pool_msol_leg.reload()?;
             ctx.accounts.liq_pool_token_account.reload()?;
             ctx.accounts.get_msol_from.reload()?;
             ctx.accounts.get_bsol_from.reload()?;
 
+            // keep the final state of the previous epoch's report before it is overwritten
+            if let Some(history) = &mut ctx.accounts.epoch_report_history {
+                history.record((&**ctx.accounts.epoch_report_account).into());
+            }
+
             // all tickets are recovered. Now we update the epoch report account to the current epoch
             ctx.accounts.epoch_report_account.epoch = ctx.accounts.clock.epoch;
             ctx.accounts.epoch_report_account.tickets = 0;
             ctx.accounts.epoch_report_account.total_ordered_lamports = 0;
+            ctx.accounts.epoch_report_account.settle_treasury_tickets();
             ctx.accounts.epoch_report_account.current_gsol_supply = ctx.accounts.gsol_mint.supply;
 
             let calculate_yield_accounts: CalculateExtractableYieldProperties =
                 ctx.accounts.deref().into();
-            let extractable_yield =
-                marinade::calculate_extractable_yield(&calculate_yield_accounts)?;
+            let extractable_yield = marinade::calculate_extractable_yield(
+                &calculate_yield_accounts,
+                entry_pools_value,
+            )?;
             msg!("Extractable yield: {}", extractable_yield);
             ctx.accounts.epoch_report_account.extractable_yield = extractable_yield;
+
+            if let Some(history) = &mut ctx.accounts.epoch_report_history {
+                history.record((&**ctx.accounts.epoch_report_account).into());
+            }
         } else {
             // more tickets to recover, but we have already recovered all the lamports
             // this is a failure state, and should only happen if something else has gone wrong
@@ -210,9 +231,14 @@ pub fn recover_tickets_handler<'info>(
     } else {
         // we have recovered some tickets, but not all of them
         // update the epoch report account to reflect this, but do not update the epoch yet
-        ctx.accounts.epoch_report_account.tickets -= ctx.remaining_accounts.len() as u64;
+        ctx.accounts.epoch_report_account.tickets -= tickets.len() as u64;
         ctx.accounts.epoch_report_account.total_ordered_lamports -= claimed_lamports;
     }
 
+    emit_cpi!(RebalanceEvent {
+        state: ctx.accounts.state.key(),
+        action: RebalanceAction::RecoverTickets,
+        lamports: claimed_lamports,
+    });
     Ok(())
 }
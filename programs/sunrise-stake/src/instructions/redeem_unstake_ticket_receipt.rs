@@ -0,0 +1,66 @@
+use crate::events::{TicketTransferAction, TicketTransferredEvent};
+use crate::state::{State, SunriseTicketAccount};
+use crate::utils::seeds::TICKET_RECEIPT_MINT;
+use crate::utils::token::burn;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Burn a ticket receipt token and make its holder the beneficiary of the ticket.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemUnstakeTicketReceipt<'info> {
+    pub state: Box<Account<'info, State>>,
+
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = sunrise_ticket_account.state_address == state.key(),
+        constraint = sunrise_ticket_account.beneficiary == ticket_receipt_mint.key(),
+    )]
+    pub sunrise_ticket_account: Account<'info, SunriseTicketAccount>,
+
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), TICKET_RECEIPT_MINT, sunrise_ticket_account.key().as_ref()],
+        bump,
+    )]
+    pub ticket_receipt_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = ticket_receipt_mint,
+        token::authority = holder,
+    )]
+    pub holder_receipt_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn redeem_unstake_ticket_receipt_handler(
+    ctx: Context<RedeemUnstakeTicketReceipt>,
+) -> Result<()> {
+    burn(
+        1,
+        &ctx.accounts.ticket_receipt_mint.to_account_info(),
+        &ctx.accounts.holder.to_account_info(),
+        &ctx.accounts.holder_receipt_token_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Redeemed ticket {} to {}",
+        ctx.accounts.sunrise_ticket_account.key(),
+        ctx.accounts.holder.key()
+    );
+    ctx.accounts.sunrise_ticket_account.beneficiary = ctx.accounts.holder.key();
+
+    emit_cpi!(TicketTransferredEvent {
+        state: ctx.accounts.state.key(),
+        ticket: ctx.accounts.sunrise_ticket_account.key(),
+        previous_beneficiary: ctx.accounts.ticket_receipt_mint.key(),
+        beneficiary: ctx.accounts.holder.key(),
+        action: TicketTransferAction::RedeemReceipt,
+    });
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::adapters::{MarinadeAdapter, SplStakePoolAdapter};
+use crate::events::{PoolAction, PoolEvent};
+use crate::state::{PoolRegistry, PoolType, State};
+use crate::utils::seeds::POOL_REGISTRY;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterPool<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = PoolRegistry::SPACE,
+        payer = payer,
+        seeds = [state.key().as_ref(), POOL_REGISTRY],
+        bump,
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
+    /// CHECK: Validated in the handler by loading it with the adapter for the pool type
+    pub pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_pool_handler(ctx: Context<RegisterPool>, pool_type: PoolType) -> Result<()> {
+    // fail early if the account is not a pool of this type
+    match pool_type {
+        PoolType::Marinade => MarinadeAdapter::load(&ctx.accounts.pool).map(|_| ())?,
+        PoolType::Spl => SplStakePoolAdapter::load(&ctx.accounts.pool).map(|_| ())?,
+    }
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.state_address = ctx.accounts.state.key();
+    pool_registry.bump = ctx.bumps.pool_registry;
+    pool_registry.register(pool_type, ctx.accounts.pool.key())?;
+    ctx.accounts.state.has_pool_registry = true;
+
+    emit_cpi!(PoolEvent {
+        state: ctx.accounts.state.key(),
+        pool: ctx.accounts.pool.key(),
+        action: PoolAction::Register,
+    });
+    Ok(())
+}
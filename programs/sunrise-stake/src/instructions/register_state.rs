@@ -1,3 +1,4 @@
+use crate::events::StateUpdatedEvent;
 use crate::state::{State, StateInput};
 use crate::utils::seeds::{BSOL_ACCOUNT, GSOL_MINT_AUTHORITY, MSOL_ACCOUNT};
 use crate::utils::token::{create_mint, create_token_account};
@@ -5,6 +6,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(state_in: StateInput)]
 pub struct RegisterState<'info> {
@@ -55,7 +57,11 @@ pub struct RegisterState<'info> {
 }
 
 pub fn register_state_handler(ctx: Context<RegisterState>, state: StateInput) -> Result<()> {
+    state.validate()?;
+
     let state_account = &mut ctx.accounts.state;
+    // a new account is initialised with the same defaults as a migrated one
+    state_account.migrate();
     state_account.set_values(&state, &ctx.accounts.mint.key());
 
     // create the gsol mint
@@ -118,5 +124,9 @@ pub fn register_state_handler(ctx: Context<RegisterState>, state: StateInput) ->
         &ctx.accounts.associated_token_program,
     )?;
 
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: ctx.accounts.state.update_authority,
+    });
     Ok(())
 }
@@ -0,0 +1,25 @@
+use crate::events::StateUpdatedEvent;
+use crate::state::State;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+
+    pub update_authority: Signer<'info>,
+}
+
+pub fn set_operator_handler(ctx: Context<SetOperator>, operator: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.state.operator = operator;
+
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: ctx.accounts.state.update_authority,
+    });
+    Ok(())
+}
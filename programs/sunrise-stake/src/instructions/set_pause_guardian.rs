@@ -0,0 +1,28 @@
+use crate::events::StateUpdatedEvent;
+use crate::state::State;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPauseGuardian<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+
+    pub update_authority: Signer<'info>,
+}
+
+pub fn set_pause_guardian_handler(
+    ctx: Context<SetPauseGuardian>,
+    pause_guardian: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.state.pause_guardian = pause_guardian;
+
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: ctx.accounts.state.update_authority,
+    });
+    Ok(())
+}
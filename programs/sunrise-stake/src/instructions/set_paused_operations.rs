@@ -0,0 +1,37 @@
+use crate::error::ErrorCode;
+use crate::events::PauseEvent;
+use crate::state::{operation, State};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPausedOperations<'info> {
+    #[account(
+        mut,
+        has_one = update_authority
+    )]
+    pub state: Account<'info, State>,
+
+    pub update_authority: Signer<'info>,
+}
+
+/// Replace the set of paused operations, pausing or unpausing any of them
+pub fn set_paused_operations_handler(
+    ctx: Context<SetPausedOperations>,
+    paused_operations: u16,
+) -> Result<()> {
+    require!(
+        paused_operations & !operation::ALL == 0,
+        ErrorCode::InvalidOperationFlags
+    );
+
+    ctx.accounts.state.paused_operations = paused_operations;
+    msg!("Paused operations: {:#06x}", paused_operations);
+
+    emit_cpi!(PauseEvent {
+        state: ctx.accounts.state.key(),
+        authority: ctx.accounts.update_authority.key(),
+        paused_operations,
+    });
+    Ok(())
+}
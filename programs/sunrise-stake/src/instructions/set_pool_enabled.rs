@@ -0,0 +1,42 @@
+use crate::events::{PoolAction, PoolEvent};
+use crate::state::{PoolRegistry, State};
+use crate::utils::seeds::POOL_REGISTRY;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolEnabled<'info> {
+    #[account(
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), POOL_REGISTRY],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+}
+
+pub fn set_pool_enabled_handler(
+    ctx: Context<SetPoolEnabled>,
+    pool: Pubkey,
+    enabled: bool,
+) -> Result<()> {
+    // Marinade and Blaze must be registered before they can be disabled
+    ctx.accounts.pool_registry.set_enabled(&pool, enabled)?;
+
+    emit_cpi!(PoolEvent {
+        state: ctx.accounts.state.key(),
+        pool,
+        action: if enabled {
+            PoolAction::Enable
+        } else {
+            PoolAction::Disable
+        },
+    });
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::events::YieldRecipientsUpdatedEvent;
+use crate::state::{State, YieldRecipient, YieldRecipients};
+use crate::utils::calc::MAX_BASIS_POINTS;
+use crate::utils::seeds::YIELD_RECIPIENTS;
+use crate::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetYieldRecipients<'info> {
+    #[account(
+        has_one = update_authority
+    )]
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub update_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        space = YieldRecipients::SPACE,
+        payer = payer,
+        seeds = [state.key().as_ref(), YIELD_RECIPIENTS],
+        bump,
+    )]
+    pub yield_recipients: Box<Account<'info, YieldRecipients>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_yield_recipients_handler(
+    ctx: Context<SetYieldRecipients>,
+    recipients: Vec<YieldRecipient>,
+) -> Result<()> {
+    require_gte!(
+        YieldRecipients::MAX_RECIPIENTS,
+        recipients.len(),
+        ErrorCode::TooManyYieldRecipients
+    );
+    let total_weight: u64 = recipients.iter().map(|r| r.weight_bps as u64).sum();
+    require_eq!(
+        total_weight,
+        MAX_BASIS_POINTS,
+        ErrorCode::InvalidYieldRecipientWeights
+    );
+
+    let yield_recipients = &mut ctx.accounts.yield_recipients;
+    yield_recipients.state_address = ctx.accounts.state.key();
+    yield_recipients.recipients = recipients.clone();
+    yield_recipients.bump = ctx.bumps.yield_recipients;
+
+    emit_cpi!(YieldRecipientsUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        recipients,
+    });
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::events::{TicketTransferAction, TicketTransferredEvent};
+use crate::state::{State, SunriseTicketAccount};
+use crate::utils::seeds::{TICKET_RECEIPT_AUTHORITY, TICKET_RECEIPT_MINT};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+/// Represent a ticket as a transferable SPL token.
+/// A mint is derived per ticket, and a single token is minted to the current beneficiary.
+/// The ticket's beneficiary is then set to the mint, so the ticket can only be claimed
+/// after the holder of the token redeems it with `redeem_unstake_ticket_receipt`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TokenizeUnstakeTicket<'info> {
+    pub state: Box<Account<'info, State>>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        constraint = sunrise_ticket_account.state_address == state.key(),
+    )]
+    pub sunrise_ticket_account: Account<'info, SunriseTicketAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        seeds = [state.key().as_ref(), TICKET_RECEIPT_MINT, sunrise_ticket_account.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = ticket_receipt_authority,
+    )]
+    pub ticket_receipt_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [state.key().as_ref(), TICKET_RECEIPT_AUTHORITY],
+        bump,
+    )]
+    pub ticket_receipt_authority: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = ticket_receipt_mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub beneficiary_receipt_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn tokenize_unstake_ticket_handler(ctx: Context<TokenizeUnstakeTicket>) -> Result<()> {
+    let state_address = ctx.accounts.state.key();
+    let seeds = &[
+        state_address.as_ref(),
+        TICKET_RECEIPT_AUTHORITY,
+        &[ctx.bumps.ticket_receipt_authority],
+    ];
+    let pda_signer = &[&seeds[..]];
+
+    let accounts = token::MintTo {
+        mint: ctx.accounts.ticket_receipt_mint.to_account_info(),
+        to: ctx
+            .accounts
+            .beneficiary_receipt_token_account
+            .to_account_info(),
+        authority: ctx.accounts.ticket_receipt_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), accounts)
+        .with_signer(pda_signer);
+    token::mint_to(cpi_ctx, 1)?;
+
+    msg!(
+        "Tokenized ticket {} with receipt mint {}",
+        ctx.accounts.sunrise_ticket_account.key(),
+        ctx.accounts.ticket_receipt_mint.key()
+    );
+    ctx.accounts.sunrise_ticket_account.beneficiary = ctx.accounts.ticket_receipt_mint.key();
+
+    emit_cpi!(TicketTransferredEvent {
+        state: ctx.accounts.state.key(),
+        ticket: ctx.accounts.sunrise_ticket_account.key(),
+        previous_beneficiary: ctx.accounts.beneficiary.key(),
+        beneficiary: ctx.accounts.ticket_receipt_mint.key(),
+        action: TicketTransferAction::Tokenize,
+    });
+    Ok(())
+}
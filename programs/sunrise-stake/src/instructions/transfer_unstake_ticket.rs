@@ -0,0 +1,37 @@
+use crate::events::{TicketTransferAction, TicketTransferredEvent};
+use crate::state::SunriseTicketAccount;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferUnstakeTicket<'info> {
+    #[account(
+        mut,
+        has_one = beneficiary,
+    )]
+    pub sunrise_ticket_account: Account<'info, SunriseTicketAccount>,
+
+    pub beneficiary: Signer<'info>,
+}
+
+pub fn transfer_unstake_ticket_handler(
+    ctx: Context<TransferUnstakeTicket>,
+    new_beneficiary: Pubkey,
+) -> Result<()> {
+    msg!(
+        "Transferring ticket {} from {} to {}",
+        ctx.accounts.sunrise_ticket_account.key(),
+        ctx.accounts.beneficiary.key(),
+        new_beneficiary
+    );
+    ctx.accounts.sunrise_ticket_account.beneficiary = new_beneficiary;
+
+    emit_cpi!(TicketTransferredEvent {
+        state: ctx.accounts.sunrise_ticket_account.state_address,
+        ticket: ctx.accounts.sunrise_ticket_account.key(),
+        previous_beneficiary: ctx.accounts.beneficiary.key(),
+        beneficiary: new_beneficiary,
+        action: TicketTransferAction::Transfer,
+    });
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use crate::error::ErrorCode;
+use crate::events::{RebalanceAction, RebalanceEvent};
 use crate::marinade::program::MarinadeFinance;
 use crate::state::{EpochReportAccount, State};
 use crate::utils::marinade;
@@ -8,6 +9,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 #[instruction(
 epoch: u64,
@@ -157,6 +159,12 @@ pub fn trigger_pool_rebalance_handler<'info>(
             .epoch_report_account
             .add_ticket(actual_lamports_to_receive, &ctx.accounts.clock)?;
         ctx.accounts.epoch_report_account.current_gsol_supply = ctx.accounts.gsol_mint.supply;
+
+        emit_cpi!(RebalanceEvent {
+            state: ctx.accounts.state.key(),
+            action: RebalanceAction::OrderDelayedUnstake,
+            lamports: actual_lamports_to_receive,
+        });
     }
 
     Ok(())
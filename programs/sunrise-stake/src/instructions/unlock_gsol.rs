@@ -1,10 +1,12 @@
 use crate::error::ErrorCode;
+use crate::events::{LockAction, LockEvent};
 use crate::state::{LockAccount, State};
 use crate::utils::seeds::{LOCK_ACCOUNT, LOCK_TOKEN_ACCOUNT};
 use crate::utils::token::transfer_to_signed;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct UnlockGSol<'info> {
     #[account(
@@ -66,5 +68,10 @@ pub fn unlock_gsol_handler(ctx: Context<UnlockGSol>) -> Result<()> {
 
     ctx.accounts.lock_account.start_epoch = None;
 
+    emit_cpi!(LockEvent::new(
+        &ctx.accounts.lock_account,
+        LockAction::Unlock,
+        lamports
+    ));
     Ok(())
 }
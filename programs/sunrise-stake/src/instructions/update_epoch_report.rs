@@ -1,13 +1,16 @@
+use crate::events::EpochReportUpdatedEvent;
 use crate::marinade::program::MarinadeFinance;
-use crate::state::{EpochReportAccount, State};
+use crate::state::{EpochReportAccount, EpochReportHistory, State};
+use crate::sunrise_spl::value_pool_entries;
 use crate::utils::marinade;
 use crate::utils::marinade::CalculateExtractableYieldProperties;
-use crate::utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, MSOL_ACCOUNT};
+use crate::utils::seeds::{BSOL_ACCOUNT, EPOCH_REPORT_ACCOUNT, EPOCH_REPORT_HISTORY, MSOL_ACCOUNT};
 use crate::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use std::ops::Deref;
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct UpdateEpochReport<'info> {
     #[account(
@@ -86,6 +89,13 @@ pub struct UpdateEpochReport<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub marinade_program: Program<'info, MarinadeFinance>,
+
+    #[account(
+    mut,
+    seeds = [state.key().as_ref(), EPOCH_REPORT_HISTORY],
+    bump = epoch_report_history.bump,
+    )]
+    pub epoch_report_history: Option<Box<Account<'info, EpochReportHistory>>>,
 }
 
 pub fn update_epoch_report_handler<'info>(
@@ -106,15 +116,32 @@ pub fn update_epoch_report_handler<'info>(
         ErrorCode::RemainingUnclaimableTicketAmount
     );
 
+    // keep the final state of the previous epoch's report before it is overwritten
+    if let Some(history) = &mut ctx.accounts.epoch_report_history {
+        history.record((&**ctx.accounts.epoch_report_account).into());
+    }
+
     ctx.accounts.epoch_report_account.epoch = ctx.accounts.clock.epoch;
     ctx.accounts.epoch_report_account.current_gsol_supply = ctx.accounts.gsol_mint.supply;
     ctx.accounts.epoch_report_account.tickets = 0;
     ctx.accounts.epoch_report_account.total_ordered_lamports = 0;
+    if is_previous_epoch {
+        ctx.accounts.epoch_report_account.settle_treasury_tickets();
+    }
 
     let calculate_yield_accounts: CalculateExtractableYieldProperties = ctx.accounts.deref().into();
-    let extractable_yield = marinade::calculate_extractable_yield(&calculate_yield_accounts)?;
+    let (entry_pools_value, _) = value_pool_entries(&ctx.accounts.state, ctx.remaining_accounts)?;
+    let extractable_yield =
+        marinade::calculate_extractable_yield(&calculate_yield_accounts, entry_pools_value)?;
     msg!("Extractable yield: {}", extractable_yield);
     ctx.accounts.epoch_report_account.extractable_yield = extractable_yield;
 
+    if let Some(history) = &mut ctx.accounts.epoch_report_history {
+        history.record((&**ctx.accounts.epoch_report_account).into());
+    }
+
+    emit_cpi!(EpochReportUpdatedEvent::from(
+        &**ctx.accounts.epoch_report_account
+    ));
     Ok(())
 }
@@ -1,4 +1,5 @@
 use crate::error::ErrorCode;
+use crate::events::{LockAction, LockEvent};
 use crate::impact_nft::accounts::GlobalState as ImpactNftState;
 use crate::impact_nft::cpi::accounts::UpdateNft;
 use crate::impact_nft::cpi::update_nft as cpi_update_nft;
@@ -11,6 +12,7 @@ use crate::utils::seeds::{
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct UpdateLockAccount<'info> {
     #[account(
@@ -101,6 +103,7 @@ pub fn update_lock_account_handler(ctx: Context<UpdateLockAccount>) -> Result<()
     ctx.accounts.lock_account.calculate_and_add_yield_accrued(
         &ctx.accounts.epoch_report_account,
         &ctx.accounts.lock_gsol_account,
+        ctx.accounts.state.estimated_unstake_fee_bps,
     )?;
 
     // ctx.accounts.lock_account.yield_accrued_by_owner
@@ -148,5 +151,12 @@ pub fn update_lock_account_handler(ctx: Context<UpdateLockAccount>) -> Result<()
         "yield accrued: {}",
         ctx.accounts.lock_account.yield_accrued_by_owner
     );
-    cpi_update_nft(cpi_ctx, ctx.accounts.lock_account.yield_accrued_by_owner)
+    cpi_update_nft(cpi_ctx, ctx.accounts.lock_account.yield_accrued_by_owner)?;
+
+    emit_cpi!(LockEvent::new(
+        &ctx.accounts.lock_account,
+        LockAction::Update,
+        0
+    ));
+    Ok(())
 }
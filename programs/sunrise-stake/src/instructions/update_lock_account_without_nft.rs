@@ -1,4 +1,5 @@
 use crate::error::ErrorCode;
+use crate::events::{LockAction, LockEvent};
 use crate::state::{EpochReportAccount, LockAccount, State};
 use crate::utils::seeds::{EPOCH_REPORT_ACCOUNT, LOCK_ACCOUNT, LOCK_TOKEN_ACCOUNT};
 use anchor_lang::prelude::*;
@@ -8,6 +9,7 @@ use anchor_spl::token::{Mint, TokenAccount};
  * This is used when the lock account is updated but the NFT no longer exists
  * This can happen if the NFT was burned or transferred.
  */
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct UpdateLockAccountWithoutNft<'info> {
     #[account(
@@ -52,7 +54,13 @@ pub fn update_lock_account_without_nft_handler(
     ctx.accounts.lock_account.calculate_and_add_yield_accrued(
         &ctx.accounts.epoch_report_account,
         &ctx.accounts.lock_gsol_account,
+        ctx.accounts.state.estimated_unstake_fee_bps,
     )?;
 
+    emit_cpi!(LockEvent::new(
+        &ctx.accounts.lock_account,
+        LockAction::Update,
+        0
+    ));
     Ok(())
 }
@@ -1,3 +1,5 @@
+use crate::error::ErrorCode;
+use crate::events::StateUpdatedEvent;
 use crate::state::{State, StateInput};
 use crate::utils::seeds::{BSOL_ACCOUNT, MSOL_ACCOUNT};
 use crate::utils::token::create_token_account;
@@ -5,6 +7,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(state_in: StateInput)]
 pub struct UpdateState<'info> {
@@ -61,6 +64,17 @@ pub struct UpdateState<'info> {
 pub fn update_state_handler(ctx: Context<UpdateState>, state: StateInput) -> Result<()> {
     // Check the liq_pool_proportion does not exceed 100%
     require_gte!(100, state.liq_pool_proportion);
+    state.validate()?;
+    require!(
+        !state.changes_timelocked_fields(&ctx.accounts.state),
+        ErrorCode::StateUpdateRequiresTimelock
+    );
+    // Changing the update authority is a two-step process, see propose_update_authority
+    require_keys_eq!(
+        state.update_authority,
+        ctx.accounts.state.update_authority,
+        ErrorCode::UpdateAuthorityChangeNotAllowed
+    );
 
     let state_account = &mut ctx.accounts.state;
     let gsol_mint = state_account.gsol_mint;
@@ -115,5 +129,9 @@ pub fn update_state_handler(ctx: Context<UpdateState>, state: StateInput) -> Res
         )?;
     }
 
+    emit_cpi!(StateUpdatedEvent {
+        state: ctx.accounts.state.key(),
+        update_authority: ctx.accounts.state.update_authority,
+    });
     Ok(())
 }
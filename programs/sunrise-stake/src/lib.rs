@@ -1,6 +1,7 @@
 #![allow(unexpected_cfgs)]
 #![allow(clippy::result_large_err)]
 #![allow(deprecated)]
+mod adapters;
 mod sunrise_spl;
 mod utils;
 
@@ -14,6 +15,8 @@ use crate::state::*;
 pub mod error;
 use crate::error::ErrorCode;
 
+pub mod events;
+
 pub mod instructions;
 use instructions::*;
 
@@ -29,6 +32,28 @@ pub mod sunrise_stake {
         deposit_handler(ctx, lamports)
     }
 
+    /// Deposit SOL into Marinade with slippage protection.
+    /// Fails if fewer than `min_gsol_out` gSOL are minted, or if more than
+    /// `max_liq_pool_share` basis points of the deposit would go to the liquidity pool.
+    pub fn deposit_v2(
+        ctx: Context<Deposit>,
+        lamports: u64,
+        min_gsol_out: u64,
+        max_liq_pool_share: u16,
+    ) -> Result<()> {
+        deposit_v2_handler(ctx, lamports, min_gsol_out, max_liq_pool_share)
+    }
+
+    /// Deposit SOL into whichever of Marinade and Blaze is furthest below its target allocation.
+    /// Fails if fewer than `min_gsol_out` gSOL are minted.
+    pub fn deposit_routed(
+        ctx: Context<DepositRouted>,
+        lamports: u64,
+        min_gsol_out: u64,
+    ) -> Result<()> {
+        deposit_routed_handler(ctx, lamports, min_gsol_out)
+    }
+
     pub fn deposit_stake_account(
         ctx: Context<DepositStakeAccount>,
         validator_index: u32,
@@ -44,10 +69,51 @@ pub mod sunrise_stake {
         claim_unstake_ticket_handler(ctx)
     }
 
+    /// Permissionless claim of a due ticket, paying out to the recorded beneficiary.
+    pub fn claim_unstake_ticket_on_behalf(ctx: Context<ClaimUnstakeTicketOnBehalf>) -> Result<()> {
+        claim_unstake_ticket_on_behalf_handler(ctx)
+    }
+
+    /// Claim multiple due tickets for the same beneficiary in one instruction.
+    /// Remaining accounts: pairs of (marinade ticket account, sunrise ticket account).
+    pub fn claim_unstake_tickets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimUnstakeTickets<'info>>,
+    ) -> Result<()> {
+        claim_unstake_tickets_handler(ctx)
+    }
+
+    /// Reassign the beneficiary of a delayed unstake ticket. Signed by the current beneficiary.
+    pub fn transfer_unstake_ticket(
+        ctx: Context<TransferUnstakeTicket>,
+        new_beneficiary: Pubkey,
+    ) -> Result<()> {
+        transfer_unstake_ticket_handler(ctx, new_beneficiary)
+    }
+
+    /// Represent a delayed unstake ticket as a single SPL token held by the beneficiary.
+    pub fn tokenize_unstake_ticket(ctx: Context<TokenizeUnstakeTicket>) -> Result<()> {
+        tokenize_unstake_ticket_handler(ctx)
+    }
+
+    /// Burn a ticket receipt token, making the holder the beneficiary of the ticket.
+    pub fn redeem_unstake_ticket_receipt(ctx: Context<RedeemUnstakeTicketReceipt>) -> Result<()> {
+        redeem_unstake_ticket_receipt_handler(ctx)
+    }
+
     pub fn liquid_unstake(ctx: Context<LiquidUnstake>, lamports: u64) -> Result<()> {
         liquid_unstake_handler(ctx, lamports)
     }
 
+    /// Liquid unstake with slippage protection.
+    /// Fails if the user's SOL balance increases by less than `min_lamports_out`.
+    pub fn liquid_unstake_v2(
+        ctx: Context<LiquidUnstake>,
+        lamports: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        liquid_unstake_v2_handler(ctx, lamports, min_lamports_out)
+    }
+
     pub fn trigger_pool_rebalance<'info>(
         ctx: Context<'_, '_, '_, 'info, TriggerPoolRebalance<'info>>,
         epoch: u64,
@@ -78,24 +144,64 @@ pub mod sunrise_stake {
         extract_to_treasury_handler(ctx)
     }
 
+    /// Extract up to `amount` lamports of yield to the treasury, capped by the extractable yield.
+    /// `mode` selects whether the yield is realised by liquid unstaking or delayed unstaking
+    /// mSOL, by withdrawing SOL from Blaze, or by a balanced split of the two.
+    pub fn extract_to_treasury_v2(
+        ctx: Context<ExtractToTreasuryV2>,
+        amount: u64,
+        mode: ExtractionMode,
+    ) -> Result<()> {
+        extract_to_treasury_v2_handler(ctx, amount, mode)
+    }
+
+    /// Extract up to `amount` lamports of yield and split it between the yield recipients.
+    /// Remaining accounts: the recipients, in the order stored in the YieldRecipients account.
+    pub fn extract_to_yield_recipients<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExtractToTreasuryV2<'info>>,
+        amount: u64,
+        mode: ExtractionMode,
+    ) -> Result<()> {
+        extract_to_yield_recipients_handler(ctx, amount, mode)
+    }
+
     //////////////////////////////////////////
     // Blaze Stake Instructions
     /////////////////////////////////////////
 
     pub fn spl_deposit_sol(ctx: Context<SplDepositSol>, amount: u64) -> Result<()> {
-        ctx.accounts.deposit_sol(amount)
+        spl_deposit_sol_handler(ctx, amount)
+    }
+
+    /// Deposit SOL into Blaze, failing if fewer than `min_gsol_out` gSOL are minted.
+    pub fn spl_deposit_sol_v2(
+        ctx: Context<SplDepositSol>,
+        amount: u64,
+        min_gsol_out: u64,
+    ) -> Result<()> {
+        spl_deposit_sol_v2_handler(ctx, amount, min_gsol_out)
     }
 
     pub fn spl_deposit_stake(ctx: Context<SplDepositStake>) -> Result<()> {
-        ctx.accounts.deposit_stake()
+        spl_deposit_stake_handler(ctx)
     }
 
     pub fn spl_withdraw_sol(ctx: Context<SplWithdrawSol>, amount: u64) -> Result<()> {
-        ctx.accounts.withdraw_sol(amount)
+        spl_withdraw_sol_handler(ctx, amount)
     }
 
     pub fn spl_withdraw_stake(ctx: Context<SplWithdrawStake>, amount: u64) -> Result<()> {
-        ctx.accounts.withdraw_stake(amount)
+        spl_withdraw_stake_handler(ctx, amount)
+    }
+
+    /// Delayed unstake through Blaze: burn gSOL and receive a deactivating stake account.
+    pub fn spl_order_unstake(ctx: Context<SplOrderUnstake>, lamports: u64) -> Result<()> {
+        spl_order_unstake_handler(ctx, lamports)
+    }
+
+    /// Close a Blaze unstake ticket and reclaim its rent.
+    pub fn spl_close_unstake_ticket(ctx: Context<SplCloseUnstakeTicket>) -> Result<()> {
+        spl_close_unstake_ticket_handler(ctx)
     }
 
     //////////////////////////////////////////
@@ -177,15 +283,117 @@ pub mod sunrise_stake {
         register_state_handler(ctx, state)
     }
 
+    /// Update the fields that are not timelocked, and create any missing token accounts.
+    /// Changes to the treasury, pools or liquidity pool proportions must be queued
+    /// with queue_state_update.
     pub fn update_state(ctx: Context<UpdateState>, state: StateInput) -> Result<()> {
         update_state_handler(ctx, state)
     }
 
-    // TODO this is only used during development, to add features to the state account
-    // without having to create a new one.
-    // Once it is stable, we should remove this function.
-    pub fn resize_state(_ctx: Context<ResizeState>, _size: u64) -> Result<()> {
-        Ok(())
+    /// Queue a change to the state, to be executed with execute_state_update once `eta`
+    /// (a unix timestamp at least `state_update_delay` seconds away) has passed.
+    pub fn queue_state_update(
+        ctx: Context<QueueStateUpdate>,
+        state: StateInput,
+        eta: i64,
+    ) -> Result<()> {
+        queue_state_update_handler(ctx, state, eta)
+    }
+
+    /// Apply the queued state change, if its eta has passed.
+    pub fn execute_state_update(ctx: Context<ExecuteStateUpdate>) -> Result<()> {
+        execute_state_update_handler(ctx)
+    }
+
+    /// Discard the queued state change.
+    pub fn cancel_state_update(ctx: Context<CancelStateUpdate>) -> Result<()> {
+        cancel_state_update_handler(ctx)
+    }
+
+    /// Propose a new update authority. It takes effect once the new key accepts it
+    /// with accept_update_authority.
+    pub fn propose_update_authority(
+        ctx: Context<ProposeUpdateAuthority>,
+        new_update_authority: Pubkey,
+    ) -> Result<()> {
+        propose_update_authority_handler(ctx, new_update_authority)
+    }
+
+    /// Accept the update authority role, signed by the key proposed in propose_update_authority.
+    pub fn accept_update_authority(ctx: Context<AcceptUpdateAuthority>) -> Result<()> {
+        accept_update_authority_handler(ctx)
+    }
+
+    /// Set or clear the operator, a key that may run the rebalancing instructions
+    /// without being able to change the configuration.
+    pub fn set_operator(ctx: Context<SetOperator>, operator: Option<Pubkey>) -> Result<()> {
+        set_operator_handler(ctx, operator)
+    }
+
+    /// Pause the given operations (a set of `state::operation` flags).
+    /// Signed by the pause guardian or the update authority.
+    pub fn pause(ctx: Context<Pause>, operations: u16) -> Result<()> {
+        pause_handler(ctx, operations)
+    }
+
+    /// Set the paused operations, unpausing any not included.
+    pub fn set_paused_operations(
+        ctx: Context<SetPausedOperations>,
+        paused_operations: u16,
+    ) -> Result<()> {
+        set_paused_operations_handler(ctx, paused_operations)
+    }
+
+    /// Set or clear the pause guardian, a key that may pause operations but nothing else.
+    pub fn set_pause_guardian(
+        ctx: Context<SetPauseGuardian>,
+        pause_guardian: Option<Pubkey>,
+    ) -> Result<()> {
+        set_pause_guardian_handler(ctx, pause_guardian)
+    }
+
+    /// Set the recipients of extracted yield and their weights in basis points.
+    pub fn set_yield_recipients(
+        ctx: Context<SetYieldRecipients>,
+        recipients: Vec<YieldRecipient>,
+    ) -> Result<()> {
+        set_yield_recipients_handler(ctx, recipients)
+    }
+
+    /// Add a liquid staking pool to the registry of pools this instance may hold funds in,
+    /// creating the registry if needed. The pool is enabled on registration.
+    /// Once the registry exists, deposits must pass it.
+    pub fn register_pool(ctx: Context<RegisterPool>, pool_type: PoolType) -> Result<()> {
+        register_pool_handler(ctx, pool_type)
+    }
+
+    /// Create the entry for a registered SPL pool other than Blaze, allowing the spl_* deposit
+    /// and withdrawal instructions to be used with it.
+    /// From then on, the instructions valuing the holdings take the entry in their remaining accounts.
+    pub fn init_pool_entry(ctx: Context<InitPoolEntry>) -> Result<()> {
+        init_pool_entry_handler(ctx)
+    }
+
+    /// Enable or disable a registered liquid staking pool. Disabled pools accept no deposits.
+    /// Marinade and Blaze accept deposits until they are registered and disabled.
+    pub fn set_pool_enabled(
+        ctx: Context<SetPoolEnabled>,
+        pool: Pubkey,
+        enabled: bool,
+    ) -> Result<()> {
+        set_pool_enabled_handler(ctx, pool, enabled)
+    }
+
+    /// Upgrade the state account to the current layout version, reallocating it if necessary
+    /// and initialising any fields added since its version.
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        migrate_state_handler(ctx)
+    }
+
+    /// Grow the epoch report account to its current size, so that it can hold the fields
+    /// added since it was created. Required once for reports created before then.
+    pub fn migrate_epoch_report(ctx: Context<MigrateEpochReport>) -> Result<()> {
+        migrate_epoch_report_handler(ctx)
     }
 
     pub fn create_metadata(
@@ -213,4 +421,16 @@ pub mod sunrise_stake {
     ) -> Result<()> {
         init_epoch_report_handler(ctx, extracted_yield)
     }
+
+    /// Create the epoch report history account, which keeps a snapshot of the epoch report
+    /// for each of the most recent epochs.
+    pub fn init_epoch_report_history(ctx: Context<InitEpochReportHistory>) -> Result<()> {
+        init_epoch_report_history_handler(ctx)
+    }
+
+    /// Report what backs gSOL, as return data.
+    /// Read-only, so can be simulated or called by other programs.
+    pub fn get_reserves(ctx: Context<GetReserves>) -> Result<Reserves> {
+        get_reserves_handler(ctx)
+    }
 }
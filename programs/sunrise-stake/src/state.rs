@@ -1,9 +1,11 @@
 use crate::error::ErrorCode;
+use crate::utils::calc::{proportional, MAX_BASIS_POINTS};
 use crate::utils::seeds::GSOL_MINT_AUTHORITY;
 use anchor_lang::prelude::borsh::BorshDeserialize;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use std::cmp::Ordering;
 
 /// The main state account for the Sunrise Stake program
 ///
@@ -12,7 +14,11 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 /// change the discriminator from the current value, breaking compatibility with
 /// all existing on-chain accounts. The discriminator is the first 8 bytes of
 /// SHA256("account:State") and is checked by Anchor when deserializing accounts.
+///
+/// New fields are added after `version`, taking their space from `reserved`, and are
+/// initialised for existing accounts by `migrate_state` (see State::migrate).
 #[account]
+#[derive(InitSpace)]
 pub struct State {
     pub marinade_state: Pubkey,
 
@@ -34,10 +40,220 @@ pub struct State {
     pub marinade_minted_gsol: u64,
     pub blaze_minted_gsol: u64,
     pub bsol_authority_bump: u8,
+
+    /// The layout version of this account. Accounts created before versioning was
+    /// introduced are version 0, and lack this and all subsequent fields.
+    pub version: u8,
+
+    /// The estimated fee (in basis points) incurred when unstaking yield,
+    /// deducted from the yield credited to lock accounts
+    pub estimated_unstake_fee_bps: u16,
+    /// The shortfall (in lamports) tolerated when recovering delayed unstake tickets,
+    /// as claimed tickets can be worth slightly less than ordered due to rounding in Marinade
+    pub recovered_margin: u64,
+    /// The key proposed as the next update authority, which must sign to accept the role
+    pub pending_update_authority: Option<Pubkey>,
+    /// A key that may run the rebalancing instructions, but not change the configuration.
+    /// The update authority may always run them too.
+    pub operator: Option<Pubkey>,
+    /// The operations currently paused, as a set of `operation` flags
+    pub paused_operations: u16,
+    /// A key that may pause operations, but not unpause them or change anything else.
+    /// The update authority may always pause and unpause.
+    pub pause_guardian: Option<Pubkey>,
+    /// The minimum notice (in seconds) between queueing a change to a timelocked field
+    /// with queue_state_update and executing it
+    pub state_update_delay: i64,
+
+    /// The maximum gSOL supply that deposits may mint up to, or 0 for no limit
+    pub max_total_gsol_supply: u64,
+    /// The maximum lamports that may be deposited in a single epoch, or 0 for no limit.
+    /// Deposits are counted in the epoch report account.
+    pub max_deposit_per_epoch: u64,
+
+    /// The target share (in basis points) of gSOL backed by Marinade, used to route deposits.
+    /// The marinade and blaze targets add up to 10000, or are both 0 if not set.
+    pub marinade_target_bps: u16,
+    /// The target share (in basis points) of gSOL backed by Blaze, used to route deposits
+    pub blaze_target_bps: u16,
+    /// Set once the pool registry has been created by register_pool.
+    /// From then on, deposits must pass the registry, so that disabled pools can't be deposited into.
+    pub has_pool_registry: bool,
+    /// The number of pool entries created by init_pool_entry.
+    /// Their holdings are valued along with Marinade's and Blaze's, see value_pool_entries.
+    pub pool_entries: u8,
+
+    /// Space for future fields
+    pub reserved: [u8; 125],
 }
 
 impl State {
-    pub const SPACE: usize = 32 + 32 + 32 + 32 + 1 + 1 + 1 + 1 + 32 + 8 + 8 + 1 + 8 /* DISCRIMINATOR */ ;
+    pub const SPACE: usize = State::INIT_SPACE + 8 /* DISCRIMINATOR */ ;
+
+    pub const CURRENT_VERSION: u8 = 2;
+
+    /// Estimated 0.3% unstake fee
+    pub const DEFAULT_ESTIMATED_UNSTAKE_FEE_BPS: u16 = 30;
+    pub const MAX_ESTIMATED_UNSTAKE_FEE_BPS: u16 = 1_000;
+
+    pub const DEFAULT_RECOVERED_MARGIN: u64 = 10;
+    pub const MAX_RECOVERED_MARGIN: u64 = 1_000;
+
+    /// Two days
+    pub const DEFAULT_STATE_UPDATE_DELAY: i64 = 2 * 24 * 60 * 60;
+    /// Thirty days
+    pub const MAX_STATE_UPDATE_DELAY: i64 = 30 * 24 * 60 * 60;
+
+    /// Propose a new update authority. The change only takes effect once the
+    /// proposed key accepts it, so a mistyped key cannot lock out the admin.
+    pub fn propose_update_authority(&mut self, new_update_authority: Pubkey) {
+        self.pending_update_authority = Some(new_update_authority);
+    }
+
+    /// Make the pending update authority the update authority, if `signer` is the pending key
+    pub fn accept_update_authority(&mut self, signer: &Pubkey) -> Result<()> {
+        require!(
+            self.pending_update_authority == Some(*signer),
+            ErrorCode::NotPendingUpdateAuthority
+        );
+        self.update_authority = *signer;
+        self.pending_update_authority = None;
+        Ok(())
+    }
+
+    /// True if `key` may run the rebalancing instructions
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        *key == self.update_authority || self.operator == Some(*key)
+    }
+
+    /// True if `key` may pause operations
+    pub fn is_pause_guardian(&self, key: &Pubkey) -> bool {
+        *key == self.update_authority || self.pause_guardian == Some(*key)
+    }
+
+    /// Fail with OperationPaused if any of the `operations` flags are paused
+    pub fn check_not_paused(&self, operations: u16) -> Result<()> {
+        require!(
+            self.paused_operations & operations == 0,
+            ErrorCode::OperationPaused
+        );
+        Ok(())
+    }
+
+    /// Count a deposit of `lamports` in the epoch report, failing if it would exceed either deposit cap.
+    /// The report may be omitted (e.g. before it has been migrated) as long as no per-epoch limit is set.
+    pub fn record_deposit(
+        &self,
+        epoch_report: Option<&mut Account<EpochReportAccount>>,
+        gsol_mint: &Mint,
+        lamports: u64,
+    ) -> Result<()> {
+        let deposited_this_epoch = match epoch_report {
+            Some(epoch_report) => epoch_report.add_deposit(lamports, Clock::get()?.epoch)?,
+            None => {
+                require_eq!(
+                    self.max_deposit_per_epoch,
+                    0,
+                    ErrorCode::EpochReportRequired
+                );
+                lamports
+            }
+        };
+        self.check_deposit_caps(lamports, gsol_mint.supply, deposited_this_epoch)
+    }
+
+    /// Check a deposit of `lamports` against the deposit caps, given the gSOL supply before it
+    /// and the lamports deposited this epoch including it
+    pub fn check_deposit_caps(
+        &self,
+        lamports: u64,
+        gsol_supply: u64,
+        deposited_this_epoch: u64,
+    ) -> Result<()> {
+        if self.max_total_gsol_supply > 0 {
+            let supply_after = gsol_supply
+                .checked_add(lamports)
+                .ok_or(ErrorCode::CalculationFailure)?;
+            require_gte!(
+                self.max_total_gsol_supply,
+                supply_after,
+                ErrorCode::GsolSupplyCapExceeded
+            );
+        }
+
+        if self.max_deposit_per_epoch > 0 {
+            require_gte!(
+                self.max_deposit_per_epoch,
+                deposited_this_epoch,
+                ErrorCode::EpochDepositLimitExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Check that `pool` accepts deposits, given the pool registry if it was passed.
+    /// Marinade and Blaze predate the registry, so accept deposits unless registered and disabled.
+    /// Any other pool must be registered, enabled, and of the type handled by the instruction.
+    pub fn check_accepts_deposits(
+        &self,
+        pool_registry: Option<&PoolRegistry>,
+        pool: &Pubkey,
+        pool_type: PoolType,
+    ) -> Result<()> {
+        let predates_registry = *pool == self.marinade_state || *pool == self.blaze_state;
+        let registered = match pool_registry {
+            Some(pool_registry) => pool_registry.find(pool),
+            None => {
+                require!(!self.has_pool_registry, ErrorCode::MissingPoolRegistry);
+                None
+            }
+        };
+        match registered {
+            Some(registered) => {
+                require!(
+                    registered.pool_type == pool_type,
+                    ErrorCode::InvalidPoolType
+                );
+                require!(registered.enabled, ErrorCode::PoolNotEnabled);
+            }
+            None => require!(predates_registry, ErrorCode::PoolNotRegistered),
+        }
+        Ok(())
+    }
+
+    /// The pool a routed deposit should go to: the one furthest below its target allocation,
+    /// measured by the gSOL minted against each pool, skipping a pool that does not accept deposits.
+    pub fn deposit_route(
+        &self,
+        marinade_enabled: bool,
+        blaze_enabled: bool,
+    ) -> Result<DepositPool> {
+        require!(
+            self.marinade_target_bps as u64 + self.blaze_target_bps as u64 == MAX_BASIS_POINTS,
+            ErrorCode::TargetAllocationNotSet
+        );
+        match (marinade_enabled, blaze_enabled) {
+            (true, true) => {}
+            (true, false) => return Ok(DepositPool::Marinade),
+            (false, true) => return Ok(DepositPool::Blaze),
+            (false, false) => return err!(ErrorCode::PoolNotEnabled),
+        }
+
+        let total = self.marinade_minted_gsol as u128 + self.blaze_minted_gsol as u128;
+        let marinade_target = total * self.marinade_target_bps as u128;
+        let marinade_actual = self.marinade_minted_gsol as u128 * MAX_BASIS_POINTS as u128;
+
+        // With two pools, one pool is as far below target as the other is above it
+        let pool = match marinade_actual.cmp(&marinade_target) {
+            Ordering::Less => DepositPool::Marinade,
+            Ordering::Greater => DepositPool::Blaze,
+            Ordering::Equal if self.marinade_target_bps >= self.blaze_target_bps => {
+                DepositPool::Marinade
+            }
+            Ordering::Equal => DepositPool::Blaze,
+        };
+        Ok(pool)
+    }
 
     pub fn set_values(&mut self, input: &StateInput, gsol_mint: &Pubkey) {
         self.marinade_state = input.marinade_state;
@@ -56,10 +272,149 @@ impl State {
         if let Some(val) = input.blaze_minted_gsol {
             self.blaze_minted_gsol = val;
         }
+        if let Some(val) = input.estimated_unstake_fee_bps {
+            self.estimated_unstake_fee_bps = val;
+        }
+        if let Some(val) = input.recovered_margin {
+            self.recovered_margin = val;
+        }
+        if let Some(val) = input.state_update_delay {
+            self.state_update_delay = val;
+        }
+        if let Some(val) = input.max_total_gsol_supply {
+            self.max_total_gsol_supply = val;
+        }
+        if let Some(val) = input.max_deposit_per_epoch {
+            self.max_deposit_per_epoch = val;
+        }
+        if let (Some(marinade), Some(blaze)) = (input.marinade_target_bps, input.blaze_target_bps) {
+            self.marinade_target_bps = marinade;
+            self.blaze_target_bps = blaze;
+        }
+    }
+
+    /// Apply a change queued with queue_state_update, leaving all other fields as they are
+    pub fn set_timelocked_values(&mut self, input: &TimelockedStateInput) {
+        self.marinade_state = input.marinade_state;
+        self.blaze_state = input.blaze_state;
+        self.treasury = input.treasury;
+        self.gsol_mint_authority_bump = input.gsol_mint_authority_bump;
+        self.msol_authority_bump = input.msol_authority_bump;
+        self.bsol_authority_bump = input.bsol_authority_bump;
+        self.liq_pool_proportion = input.liq_pool_proportion;
+        self.liq_pool_min_proportion = input.liq_pool_min_proportion;
+        self.state_update_delay = input.state_update_delay;
+    }
+
+    /// Deserialize a state account of any version, including the unversioned legacy layout
+    pub fn try_deserialize_any_version(data: &[u8]) -> Result<Self> {
+        if data.len() >= Self::SPACE {
+            return Self::try_deserialize(&mut &data[..]);
+        }
+
+        require!(
+            data.len() >= 8 && data[..8] == *Self::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        let legacy = LegacyState::deserialize(&mut &data[8..])?;
+        Ok(legacy.into())
+    }
+
+    /// Initialise the fields added in each version after `self.version`,
+    /// and bring the account up to the current version
+    pub fn migrate(&mut self) {
+        if self.version < 1 {
+            self.estimated_unstake_fee_bps = Self::DEFAULT_ESTIMATED_UNSTAKE_FEE_BPS;
+            self.recovered_margin = Self::DEFAULT_RECOVERED_MARGIN;
+        }
+        if self.version < 2 {
+            self.state_update_delay = Self::DEFAULT_STATE_UPDATE_DELAY;
+        }
+        self.version = Self::CURRENT_VERSION;
+    }
+}
+
+/// The pools that deposits can be routed to by deposit_routed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositPool {
+    Marinade,
+    Blaze,
+}
+
+/// Flags for the operations that can be paused with State::paused_operations
+pub mod operation {
+    pub const DEPOSIT: u16 = 1 << 0;
+    pub const DEPOSIT_STAKE_ACCOUNT: u16 = 1 << 1;
+    pub const LIQUID_UNSTAKE: u16 = 1 << 2;
+    pub const ORDER_UNSTAKE: u16 = 1 << 3;
+    /// Locking and adding to a lock. Unlocking is never paused, so locked gSOL can't be trapped
+    pub const LOCK: u16 = 1 << 4;
+    pub const SPL_DEPOSIT: u16 = 1 << 5;
+    pub const SPL_WITHDRAW: u16 = 1 << 6;
+    pub const EXTRACT: u16 = 1 << 7;
+
+    pub const ALL: u16 = DEPOSIT
+        | DEPOSIT_STAKE_ACCOUNT
+        | LIQUID_UNSTAKE
+        | ORDER_UNSTAKE
+        | LOCK
+        | SPL_DEPOSIT
+        | SPL_WITHDRAW
+        | EXTRACT;
+}
+
+/// The layout of State before versioning was introduced (version 0)
+#[derive(AnchorDeserialize)]
+struct LegacyState {
+    marinade_state: Pubkey,
+    update_authority: Pubkey,
+    gsol_mint: Pubkey,
+    treasury: Pubkey,
+    gsol_mint_authority_bump: u8,
+    msol_authority_bump: u8,
+    liq_pool_proportion: u8,
+    liq_pool_min_proportion: u8,
+    blaze_state: Pubkey,
+    marinade_minted_gsol: u64,
+    blaze_minted_gsol: u64,
+    bsol_authority_bump: u8,
+}
+
+impl From<LegacyState> for State {
+    fn from(legacy: LegacyState) -> Self {
+        Self {
+            marinade_state: legacy.marinade_state,
+            update_authority: legacy.update_authority,
+            gsol_mint: legacy.gsol_mint,
+            treasury: legacy.treasury,
+            gsol_mint_authority_bump: legacy.gsol_mint_authority_bump,
+            msol_authority_bump: legacy.msol_authority_bump,
+            liq_pool_proportion: legacy.liq_pool_proportion,
+            liq_pool_min_proportion: legacy.liq_pool_min_proportion,
+            blaze_state: legacy.blaze_state,
+            marinade_minted_gsol: legacy.marinade_minted_gsol,
+            blaze_minted_gsol: legacy.blaze_minted_gsol,
+            bsol_authority_bump: legacy.bsol_authority_bump,
+            version: 0,
+            estimated_unstake_fee_bps: 0,
+            recovered_margin: 0,
+            pending_update_authority: None,
+            operator: None,
+            paused_operations: 0,
+            pause_guardian: None,
+            state_update_delay: 0,
+            max_total_gsol_supply: 0,
+            max_deposit_per_epoch: 0,
+            marinade_target_bps: 0,
+            blaze_target_bps: 0,
+            has_pool_registry: false,
+            pool_entries: 0,
+            reserved: [0; 125],
+        }
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct StateInput {
     pub marinade_state: Pubkey,
     pub blaze_state: Pubkey,
@@ -72,6 +427,142 @@ pub struct StateInput {
     pub liq_pool_min_proportion: u8,
     pub marinade_minted_gsol: Option<u64>,
     pub blaze_minted_gsol: Option<u64>,
+    pub estimated_unstake_fee_bps: Option<u16>,
+    pub recovered_margin: Option<u64>,
+    pub state_update_delay: Option<i64>,
+    /// Set to 0 to remove the limit
+    pub max_total_gsol_supply: Option<u64>,
+    /// Set to 0 to remove the limit
+    pub max_deposit_per_epoch: Option<u64>,
+    /// The target allocations must be set together, and add up to 10000
+    pub marinade_target_bps: Option<u16>,
+    pub blaze_target_bps: Option<u16>,
+}
+
+impl StateInput {
+    /// Check that the tunable parameters are within bounds
+    pub fn validate(&self) -> Result<()> {
+        if let Some(fee_bps) = self.estimated_unstake_fee_bps {
+            require_gte!(
+                State::MAX_ESTIMATED_UNSTAKE_FEE_BPS,
+                fee_bps,
+                ErrorCode::EstimatedUnstakeFeeOutOfBounds
+            );
+        }
+        if let Some(margin) = self.recovered_margin {
+            require_gte!(
+                State::MAX_RECOVERED_MARGIN,
+                margin,
+                ErrorCode::RecoveredMarginOutOfBounds
+            );
+        }
+        match (self.marinade_target_bps, self.blaze_target_bps) {
+            (None, None) => {}
+            (Some(marinade), Some(blaze)) => require_eq!(
+                marinade as u64 + blaze as u64,
+                MAX_BASIS_POINTS,
+                ErrorCode::InvalidTargetAllocation
+            ),
+            _ => return err!(ErrorCode::InvalidTargetAllocation),
+        }
+        if let Some(delay) = self.state_update_delay {
+            require!(
+                (0..=State::MAX_STATE_UPDATE_DELAY).contains(&delay),
+                ErrorCode::StateUpdateDelayOutOfBounds
+            );
+        }
+        Ok(())
+    }
+
+    /// True if applying this input would change a field that users are given notice of,
+    /// which can only be done through queue_state_update and execute_state_update
+    pub fn changes_timelocked_fields(&self, state: &State) -> bool {
+        TimelockedStateInput::new(self, state) != TimelockedStateInput::from(state)
+    }
+
+    /// True if this input sets a field that is not timelocked,
+    /// which can only be done through update_state
+    pub fn sets_untimelocked_fields(&self) -> bool {
+        self.marinade_minted_gsol.is_some()
+            || self.blaze_minted_gsol.is_some()
+            || self.estimated_unstake_fee_bps.is_some()
+            || self.recovered_margin.is_some()
+            || self.max_total_gsol_supply.is_some()
+            || self.max_deposit_per_epoch.is_some()
+            || self.marinade_target_bps.is_some()
+            || self.blaze_target_bps.is_some()
+    }
+}
+
+/// The fields of StateInput that users are given notice of before they change.
+/// Only these are stored in a PendingStateChange, so executing it cannot overwrite
+/// anything else that has changed in the meantime.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct TimelockedStateInput {
+    pub marinade_state: Pubkey,
+    pub blaze_state: Pubkey,
+    pub treasury: Pubkey,
+    pub gsol_mint_authority_bump: u8,
+    pub msol_authority_bump: u8,
+    pub bsol_authority_bump: u8,
+    pub liq_pool_proportion: u8,
+    pub liq_pool_min_proportion: u8,
+    pub state_update_delay: i64,
+}
+
+impl From<&State> for TimelockedStateInput {
+    fn from(state: &State) -> Self {
+        Self {
+            marinade_state: state.marinade_state,
+            blaze_state: state.blaze_state,
+            treasury: state.treasury,
+            gsol_mint_authority_bump: state.gsol_mint_authority_bump,
+            msol_authority_bump: state.msol_authority_bump,
+            bsol_authority_bump: state.bsol_authority_bump,
+            liq_pool_proportion: state.liq_pool_proportion,
+            liq_pool_min_proportion: state.liq_pool_min_proportion,
+            state_update_delay: state.state_update_delay,
+        }
+    }
+}
+
+impl TimelockedStateInput {
+    /// The timelocked fields of `input`, keeping the current delay of `state` if the input has none
+    pub fn new(input: &StateInput, state: &State) -> Self {
+        Self {
+            marinade_state: input.marinade_state,
+            blaze_state: input.blaze_state,
+            treasury: input.treasury,
+            gsol_mint_authority_bump: input.gsol_mint_authority_bump,
+            msol_authority_bump: input.msol_authority_bump,
+            bsol_authority_bump: input.bsol_authority_bump,
+            liq_pool_proportion: input.liq_pool_proportion,
+            liq_pool_min_proportion: input.liq_pool_min_proportion,
+            state_update_delay: input.state_update_delay.unwrap_or(state.state_update_delay),
+        }
+    }
+}
+
+/// A change to the state queued by queue_state_update, to be applied by
+/// execute_state_update once `eta` has passed
+#[account]
+#[derive(InitSpace)]
+pub struct PendingStateChange {
+    pub state_address: Pubkey,
+    /// The update authority that queued the change
+    pub update_authority: Pubkey,
+    pub input: TimelockedStateInput,
+    /// The unix timestamp from which the change can be executed
+    pub eta: i64,
+    pub bump: u8,
+}
+
+impl PendingStateChange {
+    pub const SPACE: usize = PendingStateChange::INIT_SPACE + 8 /* DISCRIMINATOR */ ;
+
+    pub fn is_ready(&self, now: i64) -> bool {
+        now >= self.eta
+    }
 }
 
 /// Maps a marinade ticket account to a GSOL token holder
@@ -85,6 +576,137 @@ impl SunriseTicketAccount {
     pub const SPACE: usize = 32 + 32 + 32 + 8 /* DISCRIMINATOR */ ;
 }
 
+/// Records a deactivating stake account withdrawn from the Blaze pool for a GSOL token holder
+#[account]
+pub struct SplUnstakeTicketAccount {
+    pub state_address: Pubkey, // instance of sunrise state this ticket belongs to
+    pub stake_account: Pubkey,
+    pub beneficiary: Pubkey,
+    pub lamports: u64, // lamports in the stake account at the time of the withdrawal
+    pub created_epoch: u64, // the stake account can be withdrawn from once this epoch has passed
+}
+impl SplUnstakeTicketAccount {
+    pub const SPACE: usize = 32 + 32 + 32 + 8 + 8 + 8 /* DISCRIMINATOR */ ;
+}
+
+/// A recipient of extracted yield, and its share of the yield in basis points
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct YieldRecipient {
+    pub recipient: Pubkey,
+    pub weight_bps: u16,
+}
+impl YieldRecipient {
+    pub const SPACE: usize = 32 + 2;
+}
+
+/// The recipients that extracted yield is split between
+#[account]
+pub struct YieldRecipients {
+    pub state_address: Pubkey,
+    pub recipients: Vec<YieldRecipient>,
+    /// Lamports held back on the msol authority PDA from earlier extractions,
+    /// because they were too little to open an empty recipient account.
+    /// They are added to the next extraction.
+    pub carried_lamports: u64,
+    pub bump: u8,
+}
+impl YieldRecipients {
+    pub const MAX_RECIPIENTS: usize = 10;
+    pub const SPACE: usize =
+        32 + (4 + Self::MAX_RECIPIENTS * YieldRecipient::SPACE) + 8 + 1 + 8 /* DISCRIMINATOR */ ;
+}
+
+/// The program that runs a liquid staking pool, which determines the adapter used to talk to it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolType {
+    Marinade,
+    Spl,
+}
+
+/// A liquid staking pool that this sunrise instance can hold funds in
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisteredPool {
+    pub pool_type: PoolType,
+    /// The pool's state account
+    pub pool: Pubkey,
+    pub enabled: bool,
+}
+impl RegisteredPool {
+    pub const SPACE: usize = 1 + 32 + 1;
+}
+
+/// The liquid staking pools registered with this sunrise instance
+#[account]
+pub struct PoolRegistry {
+    pub state_address: Pubkey,
+    pub pools: Vec<RegisteredPool>,
+    pub bump: u8,
+}
+impl PoolRegistry {
+    pub const MAX_POOLS: usize = 10;
+    pub const SPACE: usize =
+        32 + (4 + Self::MAX_POOLS * RegisteredPool::SPACE) + 1 + 8 /* DISCRIMINATOR */ ;
+
+    pub fn find(&self, pool: &Pubkey) -> Option<&RegisteredPool> {
+        self.pools
+            .iter()
+            .find(|registered| registered.pool == *pool)
+    }
+
+    pub fn is_enabled(&self, pool: &Pubkey) -> bool {
+        self.find(pool).is_some_and(|registered| registered.enabled)
+    }
+
+    pub fn enabled_pools(&self) -> impl Iterator<Item = &RegisteredPool> {
+        self.pools.iter().filter(|registered| registered.enabled)
+    }
+
+    /// Add a pool to the registry, enabled
+    pub fn register(&mut self, pool_type: PoolType, pool: Pubkey) -> Result<()> {
+        require!(self.find(&pool).is_none(), ErrorCode::PoolAlreadyRegistered);
+        require_gt!(
+            Self::MAX_POOLS,
+            self.pools.len(),
+            ErrorCode::TooManyRegisteredPools
+        );
+        self.pools.push(RegisteredPool {
+            pool_type,
+            pool,
+            enabled: true,
+        });
+        Ok(())
+    }
+
+    pub fn set_enabled(&mut self, pool: &Pubkey, enabled: bool) -> Result<()> {
+        let registered = self
+            .pools
+            .iter_mut()
+            .find(|registered| registered.pool == *pool)
+            .ok_or(ErrorCode::PoolNotRegistered)?;
+        registered.enabled = enabled;
+        Ok(())
+    }
+}
+
+/// A registered SPL stake pool other than Blaze.
+/// Blaze predates the registry, so its pool tokens and minted gSOL are tracked on the state account.
+#[account]
+pub struct PoolEntry {
+    pub state_address: Pubkey,
+    /// The stake pool account
+    pub pool: Pubkey,
+    /// The pool token mint
+    pub pool_mint: Pubkey,
+    /// Bump of the PDA holding this instance's pool tokens, seeded by the pool key
+    pub authority_bump: u8,
+    /// The gSOL minted against deposits into this pool, as blaze_minted_gsol is for Blaze
+    pub minted_gsol: u64,
+    pub bump: u8,
+}
+impl PoolEntry {
+    pub const SPACE: usize = 32 + 32 + 32 + 1 + 8 + 1 + 8 /* DISCRIMINATOR */ ;
+}
+
 #[account]
 pub struct EpochReportAccount {
     pub state_address: Pubkey,
@@ -95,14 +717,31 @@ pub struct EpochReportAccount {
     pub extracted_yield: u64,
     pub current_gsol_supply: u64,
     pub bump: u8,
+
+    // Fields below were added after the account was first deployed, see migrate_epoch_report
+    /// Delayed unstake tickets ordered this epoch when extracting yield.
+    /// Unlike `tickets`, these are claimed to the extraction recipient with
+    /// claim_unstake_ticket_on_behalf, rather than recovered into the liquidity pool.
+    pub treasury_tickets: u64,
+    /// Yield ordered for delayed unstake this epoch, counted as extracted once the epoch is over
+    pub treasury_ordered_lamports: u64,
+    /// The epoch that `deposited_lamports` refers to. Unlike `epoch`, this moves on
+    /// with the first deposit of each epoch, whether or not the report has been updated.
+    pub deposit_epoch: u64,
+    /// The lamports deposited so far in `deposit_epoch`, limited by State::max_deposit_per_epoch
+    pub deposited_lamports: u64,
 }
 impl EpochReportAccount {
-    pub const SPACE: usize = 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 /* DISCRIMINATOR */ ;
+    pub const SPACE: usize =
+        32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 /* DISCRIMINATOR */ ;
 
+    /// All yield accrued so far, whether still held, extracted, or ordered for delayed unstake
     pub fn all_extractable_yield(&self) -> u64 {
         self.extractable_yield
             .checked_add(self.extracted_yield)
             .unwrap()
+            .checked_add(self.treasury_ordered_lamports)
+            .unwrap()
     }
 
     pub fn add_ticket(&mut self, ticket_amount_lamports: u64, clock: &Sysvar<Clock>) -> Result<()> {
@@ -119,10 +758,50 @@ impl EpochReportAccount {
         Ok(())
     }
 
+    pub fn add_treasury_ticket(
+        &mut self,
+        ticket_amount_lamports: u64,
+        clock: &Sysvar<Clock>,
+    ) -> Result<()> {
+        require_eq!(
+            self.epoch,
+            clock.epoch,
+            ErrorCode::InvalidEpochReportAccount
+        );
+        self.treasury_tickets = self.treasury_tickets.checked_add(1).unwrap();
+        self.treasury_ordered_lamports = self
+            .treasury_ordered_lamports
+            .checked_add(ticket_amount_lamports)
+            .unwrap();
+        Ok(())
+    }
+
+    /// Add a deposit made in `epoch`, starting the count afresh in a new epoch.
+    /// Returns the lamports deposited in the epoch so far.
+    pub fn add_deposit(&mut self, lamports: u64, epoch: u64) -> Result<u64> {
+        if self.deposit_epoch != epoch {
+            self.deposit_epoch = epoch;
+            self.deposited_lamports = 0;
+        }
+        self.deposited_lamports = self
+            .deposited_lamports
+            .checked_add(lamports)
+            .ok_or(ErrorCode::CalculationFailure)?;
+        Ok(self.deposited_lamports)
+    }
+
     pub fn add_extracted_yield(&mut self, extracted_yield: u64) {
         self.extracted_yield = self.extracted_yield.checked_add(extracted_yield).unwrap();
     }
 
+    /// Count the yield ordered for delayed unstake as extracted once its epoch is over,
+    /// and its treasury tickets have become claimable.
+    pub fn settle_treasury_tickets(&mut self) {
+        self.add_extracted_yield(self.treasury_ordered_lamports);
+        self.treasury_tickets = 0;
+        self.treasury_ordered_lamports = 0;
+    }
+
     pub fn update_report(
         &mut self,
         current_gsol_supply: u64,
@@ -146,6 +825,98 @@ pub struct TicketAccountData {
     pub created_epoch: u64, // epoch when this acc was created (epoch when delayed-unstake was requested)
 }
 
+/// A snapshot of the epoch report account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochReportHistoryEntry {
+    pub epoch: u64,
+    pub tickets: u64,
+    pub total_ordered_lamports: u64,
+    pub extractable_yield: u64,
+    pub extracted_yield: u64,
+    pub current_gsol_supply: u64,
+    pub treasury_tickets: u64,
+    pub treasury_ordered_lamports: u64,
+    /// The lamports deposited during `epoch`
+    pub deposited_lamports: u64,
+}
+impl EpochReportHistoryEntry {
+    pub const SPACE: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
+}
+impl From<&EpochReportAccount> for EpochReportHistoryEntry {
+    fn from(report: &EpochReportAccount) -> Self {
+        Self {
+            epoch: report.epoch,
+            tickets: report.tickets,
+            total_ordered_lamports: report.total_ordered_lamports,
+            extractable_yield: report.extractable_yield,
+            extracted_yield: report.extracted_yield,
+            current_gsol_supply: report.current_gsol_supply,
+            treasury_tickets: report.treasury_tickets,
+            treasury_ordered_lamports: report.treasury_ordered_lamports,
+            // the deposit count may already have moved on to a later epoch
+            deposited_lamports: if report.deposit_epoch == report.epoch {
+                report.deposited_lamports
+            } else {
+                0
+            },
+        }
+    }
+}
+
+/// A ring buffer of the most recent epoch reports, one entry per epoch
+#[account]
+pub struct EpochReportHistory {
+    pub state_address: Pubkey,
+    /// The index that the next new epoch will be written to
+    pub next_index: u16,
+    pub entries: Vec<EpochReportHistoryEntry>,
+    pub bump: u8,
+}
+impl EpochReportHistory {
+    pub const CAPACITY: usize = 128;
+    pub const SPACE: usize = 32
+        + 2
+        + (4 + Self::CAPACITY * EpochReportHistoryEntry::SPACE)
+        + 1
+        + 8 /* DISCRIMINATOR */ ;
+
+    fn latest_index(&self) -> Option<usize> {
+        let len = self.entries.len();
+        if len == 0 {
+            return None;
+        }
+        Some((self.next_index as usize + len - 1) % len)
+    }
+
+    pub fn latest(&self) -> Option<&EpochReportHistoryEntry> {
+        self.latest_index().map(|index| &self.entries[index])
+    }
+
+    pub fn find(&self, epoch: u64) -> Option<&EpochReportHistoryEntry> {
+        self.entries.iter().find(|entry| entry.epoch == epoch)
+    }
+
+    /// Record a snapshot of the report.
+    /// If the latest entry is for the same epoch, it is replaced,
+    /// otherwise the entry is appended, overwriting the oldest entry once the buffer is full.
+    pub fn record(&mut self, entry: EpochReportHistoryEntry) {
+        if let Some(index) = self.latest_index() {
+            if self.entries[index].epoch == entry.epoch {
+                self.entries[index] = entry;
+                return;
+            }
+        }
+
+        let index = self.next_index as usize;
+        if self.entries.len() < Self::CAPACITY {
+            self.entries.push(entry);
+        } else {
+            self.entries[index] = entry;
+        }
+        self.next_index = ((index + 1) % Self::CAPACITY) as u16;
+    }
+}
+
 #[derive(Accounts, Clone)]
 pub struct CreateMetadata<'info> {
     #[account(
@@ -186,26 +957,6 @@ pub struct CreateMetadata<'info> {
     pub token_metadata_program: AccountInfo<'info>,
 }
 
-#[derive(Accounts)]
-#[instruction(size: u64)]
-pub struct ResizeState<'info> {
-    #[account(
-    mut,
-    has_one = update_authority,
-    realloc = size as usize,
-    realloc::payer = payer,
-    realloc::zero = false,
-    )]
-    pub state: Account<'info, State>,
-
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    pub update_authority: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
-
 #[account]
 pub struct LockAccount {
     pub state_address: Pubkey,
@@ -222,10 +973,30 @@ pub struct LockAccount {
 impl LockAccount {
     pub const SPACE: usize = 32 + 32 + 32 + 9 + 9 + 8 + 8 + 1 + 8 /* DISCRIMINATOR */ ;
 
+    /// The owner's share of `yield_accrued`, after deducting the estimated unstake fee,
+    /// in proportion to the owner's share of the gSOL supply
+    pub fn owner_yield_share(
+        yield_accrued: u64,
+        estimated_unstake_fee_bps: u16,
+        locked_gsol: u64,
+        gsol_supply: u64,
+    ) -> Result<u64> {
+        let fee_bps = estimated_unstake_fee_bps as u64;
+        require_gte!(MAX_BASIS_POINTS, fee_bps, ErrorCode::InvalidBasisPoints);
+        if gsol_supply == 0 {
+            return Ok(0);
+        }
+
+        let yield_accrued_with_unstake_fee =
+            proportional(yield_accrued, MAX_BASIS_POINTS - fee_bps, MAX_BASIS_POINTS)?;
+        proportional(yield_accrued_with_unstake_fee, locked_gsol, gsol_supply)
+    }
+
     pub fn calculate_and_add_yield_accrued(
         &mut self,
         epoch_report_account: &EpochReportAccount,
         locked_gsol_token_account: &Account<'_, TokenAccount>,
+        estimated_unstake_fee_bps: u16,
     ) -> Result<u64> {
         require_keys_eq!(
             self.state_address,
@@ -244,20 +1015,24 @@ impl LockAccount {
             .checked_sub(self.sunrise_yield_at_start)
             .unwrap();
 
-        let yield_accrued_with_unstake_fee = (yield_accrued as f64) * 0.997; // estimated 0.3% unstake fee
-
         msg!("total yield at start of lock period: {}\ntotal yield at end of lock period: {}\nyield_accrued: {}",
             self.sunrise_yield_at_start,
             epoch_report_account.all_extractable_yield(),
-            yield_accrued_with_unstake_fee
+            yield_accrued
         );
 
-        let owner_locked_gsol_share = (locked_gsol_token_account.amount as f64)
-            / epoch_report_account.current_gsol_supply as f64;
-
-        msg!("owner_locked_gsol_share: {}", owner_locked_gsol_share);
+        msg!(
+            "owner locked gsol: {} of {}",
+            locked_gsol_token_account.amount,
+            epoch_report_account.current_gsol_supply
+        );
 
-        let yield_accrued = (yield_accrued_with_unstake_fee * owner_locked_gsol_share) as u64;
+        let yield_accrued = Self::owner_yield_share(
+            yield_accrued,
+            estimated_unstake_fee_bps,
+            locked_gsol_token_account.amount,
+            epoch_report_account.current_gsol_supply,
+        )?;
 
         self.yield_accrued_by_owner = self
             .yield_accrued_by_owner
@@ -277,3 +1052,745 @@ impl LockAccount {
         Ok(yield_accrued)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures::load_account_data;
+
+    /// The state account from the test fixtures
+    fn fixture_state() -> State {
+        let data = load_account_data(include_str!(
+            "../../../packages/tests/fixtures/scenarios/sunrise_state.json"
+        ));
+        State::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    fn entry(epoch: u64) -> EpochReportHistoryEntry {
+        EpochReportHistoryEntry {
+            epoch,
+            extractable_yield: epoch * 10,
+            ..Default::default()
+        }
+    }
+
+    fn empty_history() -> EpochReportHistory {
+        EpochReportHistory {
+            state_address: Pubkey::default(),
+            next_index: 0,
+            entries: vec![],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_history_records_in_order() {
+        let mut history = empty_history();
+        assert!(history.latest().is_none());
+
+        history.record(entry(1));
+        history.record(entry(2));
+
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.latest().unwrap().epoch, 2);
+        assert_eq!(history.find(1).unwrap().extractable_yield, 10);
+    }
+
+    #[test]
+    fn test_history_replaces_same_epoch() {
+        let mut history = empty_history();
+        history.record(entry(1));
+        history.record(EpochReportHistoryEntry {
+            extractable_yield: 99,
+            ..entry(1)
+        });
+
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.latest().unwrap().extractable_yield, 99);
+    }
+
+    #[test]
+    fn test_history_wraps_when_full() {
+        let mut history = empty_history();
+        let capacity = EpochReportHistory::CAPACITY as u64;
+        for epoch in 0..capacity + 3 {
+            history.record(entry(epoch));
+        }
+
+        assert_eq!(history.entries.len(), EpochReportHistory::CAPACITY);
+        assert_eq!(history.next_index, 3);
+        assert_eq!(history.latest().unwrap().epoch, capacity + 2);
+        // the oldest three epochs have been overwritten
+        assert!(history.find(2).is_none());
+        assert_eq!(history.find(3).unwrap().epoch, 3);
+    }
+
+    #[test]
+    fn test_history_space_fits_full_buffer() {
+        let mut history = empty_history();
+        for epoch in 0..EpochReportHistory::CAPACITY as u64 {
+            history.record(entry(epoch));
+        }
+        let mut data = vec![];
+        history.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), EpochReportHistory::SPACE);
+        // the account is created with init, which can allocate at most this much
+        assert!(data.len() <= anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE);
+    }
+
+    fn empty_registry() -> PoolRegistry {
+        PoolRegistry {
+            state_address: Pubkey::default(),
+            pools: vec![],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_pool_registry_register_and_enable() {
+        let mut registry = empty_registry();
+        let marinade = Pubkey::new_unique();
+        let blaze = Pubkey::new_unique();
+        registry.register(PoolType::Marinade, marinade).unwrap();
+        registry.register(PoolType::Spl, blaze).unwrap();
+
+        assert!(registry.register(PoolType::Spl, blaze).is_err());
+        assert!(registry.is_enabled(&blaze));
+        assert_eq!(registry.find(&blaze).unwrap().pool_type, PoolType::Spl);
+
+        registry.set_enabled(&blaze, false).unwrap();
+        assert!(!registry.is_enabled(&blaze));
+        assert_eq!(registry.enabled_pools().count(), 1);
+
+        assert!(registry.set_enabled(&Pubkey::new_unique(), true).is_err());
+        assert!(!registry.is_enabled(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_check_accepts_deposits() {
+        let mut state = fixture_state();
+        let marinade = state.marinade_state;
+        let blaze = state.blaze_state;
+        let other = Pubkey::new_unique();
+
+        // before the registry exists, only the pools that predate it accept deposits
+        assert!(state
+            .check_accepts_deposits(None, &marinade, PoolType::Marinade)
+            .is_ok());
+        assert!(state
+            .check_accepts_deposits(None, &blaze, PoolType::Spl)
+            .is_ok());
+        assert!(state
+            .check_accepts_deposits(None, &other, PoolType::Spl)
+            .is_err());
+
+        let mut registry = empty_registry();
+        registry.register(PoolType::Spl, blaze).unwrap();
+        registry.register(PoolType::Spl, other).unwrap();
+        state.has_pool_registry = true;
+
+        // once it exists, the registry must be passed
+        assert!(state
+            .check_accepts_deposits(None, &marinade, PoolType::Marinade)
+            .is_err());
+        assert!(state
+            .check_accepts_deposits(Some(&registry), &marinade, PoolType::Marinade)
+            .is_ok());
+        assert!(state
+            .check_accepts_deposits(Some(&registry), &other, PoolType::Spl)
+            .is_ok());
+        assert!(state
+            .check_accepts_deposits(Some(&registry), &other, PoolType::Marinade)
+            .is_err());
+
+        registry.set_enabled(&blaze, false).unwrap();
+        registry.set_enabled(&other, false).unwrap();
+        assert!(state
+            .check_accepts_deposits(Some(&registry), &blaze, PoolType::Spl)
+            .is_err());
+        assert!(state
+            .check_accepts_deposits(Some(&registry), &other, PoolType::Spl)
+            .is_err());
+    }
+
+    #[test]
+    fn test_pool_registry_space_fits_full_registry() {
+        let mut registry = empty_registry();
+        for _ in 0..PoolRegistry::MAX_POOLS {
+            registry
+                .register(PoolType::Spl, Pubkey::new_unique())
+                .unwrap();
+        }
+        assert!(registry
+            .register(PoolType::Spl, Pubkey::new_unique())
+            .is_err());
+
+        let mut data = vec![];
+        registry.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PoolRegistry::SPACE);
+    }
+
+    /// The previous floating point implementation of LockAccount::owner_yield_share
+    fn owner_yield_share_f64(yield_accrued: u64, locked_gsol: u64, gsol_supply: u64) -> u64 {
+        let yield_accrued_with_unstake_fee = (yield_accrued as f64) * 0.997;
+        let owner_locked_gsol_share = (locked_gsol as f64) / gsol_supply as f64;
+        (yield_accrued_with_unstake_fee * owner_locked_gsol_share) as u64
+    }
+
+    #[test]
+    fn test_owner_yield_share_matches_f64() {
+        let cases = [
+            (0, 1, 1),
+            (1_000, 1, 1_000_000),
+            (1_000_000, 500_000, 1_000_000),
+            (123_456_789, 1_000_000_000, 3_000_000_000),
+            (5_000_000_000, 7_777_777_777, 100_000_000_000_000),
+            (250_000_000_000, 99_999_999_999_999, 100_000_000_000_000),
+            (1_000_000_000_000, 100_000_000_000_000, 100_000_000_000_000),
+        ];
+        for (yield_accrued, locked_gsol, gsol_supply) in cases {
+            let expected = owner_yield_share_f64(yield_accrued, locked_gsol, gsol_supply);
+            let actual = LockAccount::owner_yield_share(
+                yield_accrued,
+                State::DEFAULT_ESTIMATED_UNSTAKE_FEE_BPS,
+                locked_gsol,
+                gsol_supply,
+            )
+            .unwrap();
+            assert!(
+                actual.abs_diff(expected) <= 1,
+                "{} vs {} for {:?}",
+                actual,
+                expected,
+                (yield_accrued, locked_gsol, gsol_supply)
+            );
+        }
+    }
+
+    #[test]
+    fn test_owner_yield_share_does_not_overflow_on_large_supply() {
+        let share = LockAccount::owner_yield_share(u64::MAX, 0, u64::MAX, u64::MAX).unwrap();
+        assert_eq!(share, u64::MAX);
+    }
+
+    #[test]
+    fn test_owner_yield_share_edge_cases() {
+        // no gSOL supply
+        assert_eq!(LockAccount::owner_yield_share(1_000, 30, 0, 0).unwrap(), 0);
+        // the whole yield is lost to fees
+        assert_eq!(
+            LockAccount::owner_yield_share(1_000, 10_000, 10, 10).unwrap(),
+            0
+        );
+        // fee out of range
+        assert!(LockAccount::owner_yield_share(1_000, 10_001, 10, 10).is_err());
+    }
+
+    fn state_input() -> StateInput {
+        StateInput {
+            marinade_state: Pubkey::default(),
+            blaze_state: Pubkey::default(),
+            update_authority: Pubkey::default(),
+            treasury: Pubkey::default(),
+            gsol_mint_authority_bump: 0,
+            msol_authority_bump: 0,
+            bsol_authority_bump: 0,
+            liq_pool_proportion: 0,
+            liq_pool_min_proportion: 0,
+            marinade_minted_gsol: None,
+            blaze_minted_gsol: None,
+            estimated_unstake_fee_bps: None,
+            recovered_margin: None,
+            state_update_delay: None,
+            max_total_gsol_supply: None,
+            max_deposit_per_epoch: None,
+            marinade_target_bps: None,
+            blaze_target_bps: None,
+        }
+    }
+
+    #[test]
+    fn test_state_input_bounds() {
+        assert!(state_input().validate().is_ok());
+
+        let input = StateInput {
+            estimated_unstake_fee_bps: Some(State::MAX_ESTIMATED_UNSTAKE_FEE_BPS),
+            recovered_margin: Some(State::MAX_RECOVERED_MARGIN),
+            ..state_input()
+        };
+        assert!(input.validate().is_ok());
+
+        let input = StateInput {
+            estimated_unstake_fee_bps: Some(State::MAX_ESTIMATED_UNSTAKE_FEE_BPS + 1),
+            ..state_input()
+        };
+        assert!(input.validate().is_err());
+
+        let input = StateInput {
+            recovered_margin: Some(State::MAX_RECOVERED_MARGIN + 1),
+            ..state_input()
+        };
+        assert!(input.validate().is_err());
+
+        let input = StateInput {
+            state_update_delay: Some(State::MAX_STATE_UPDATE_DELAY + 1),
+            ..state_input()
+        };
+        assert!(input.validate().is_err());
+
+        let input = StateInput {
+            state_update_delay: Some(-1),
+            ..state_input()
+        };
+        assert!(input.validate().is_err());
+
+        let input = StateInput {
+            marinade_target_bps: Some(7_000),
+            blaze_target_bps: Some(3_000),
+            ..state_input()
+        };
+        assert!(input.validate().is_ok());
+
+        let input = StateInput {
+            marinade_target_bps: Some(7_000),
+            blaze_target_bps: Some(3_001),
+            ..state_input()
+        };
+        assert!(input.validate().is_err());
+
+        let input = StateInput {
+            marinade_target_bps: Some(10_000),
+            ..state_input()
+        };
+        assert!(input.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_authority_transfer() {
+        let mut state = fixture_state();
+        let old_authority = state.update_authority;
+        let new_authority = Pubkey::new_unique();
+
+        // nothing to accept before a proposal
+        assert_eq!(state.pending_update_authority, None);
+        assert!(state.accept_update_authority(&new_authority).is_err());
+
+        state.propose_update_authority(new_authority);
+        assert_eq!(state.update_authority, old_authority);
+
+        // only the proposed key can accept
+        assert!(state.accept_update_authority(&old_authority).is_err());
+        assert!(state
+            .accept_update_authority(&Pubkey::new_unique())
+            .is_err());
+
+        state.accept_update_authority(&new_authority).unwrap();
+        assert_eq!(state.update_authority, new_authority);
+        assert_eq!(state.pending_update_authority, None);
+
+        // the acceptance cannot be replayed
+        assert!(state.accept_update_authority(&new_authority).is_err());
+    }
+
+    #[test]
+    fn test_is_operator() {
+        let mut state = fixture_state();
+        let update_authority = state.update_authority;
+        let operator = Pubkey::new_unique();
+
+        // without an operator, only the update authority can rebalance
+        assert_eq!(state.operator, None);
+        assert!(state.is_operator(&update_authority));
+        assert!(!state.is_operator(&operator));
+
+        state.operator = Some(operator);
+        assert!(state.is_operator(&update_authority));
+        assert!(state.is_operator(&operator));
+        assert!(!state.is_operator(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut state = fixture_state();
+        let guardian = Pubkey::new_unique();
+
+        assert_eq!(state.paused_operations, 0);
+        assert!(state.check_not_paused(operation::ALL).is_ok());
+
+        state.paused_operations = operation::DEPOSIT | operation::SPL_DEPOSIT;
+        assert!(state.check_not_paused(operation::DEPOSIT).is_err());
+        assert!(state.check_not_paused(operation::SPL_DEPOSIT).is_err());
+        assert!(state.check_not_paused(operation::LIQUID_UNSTAKE).is_ok());
+        assert!(state.check_not_paused(operation::EXTRACT).is_ok());
+
+        assert!(state.is_pause_guardian(&state.update_authority.clone()));
+        assert!(!state.is_pause_guardian(&guardian));
+        state.pause_guardian = Some(guardian);
+        assert!(state.is_pause_guardian(&guardian));
+    }
+
+    #[test]
+    fn test_changes_timelocked_fields() {
+        let state = fixture_state();
+        let unchanged = StateInput {
+            marinade_state: state.marinade_state,
+            blaze_state: state.blaze_state,
+            update_authority: state.update_authority,
+            treasury: state.treasury,
+            gsol_mint_authority_bump: state.gsol_mint_authority_bump,
+            msol_authority_bump: state.msol_authority_bump,
+            bsol_authority_bump: state.bsol_authority_bump,
+            liq_pool_proportion: state.liq_pool_proportion,
+            liq_pool_min_proportion: state.liq_pool_min_proportion,
+            ..state_input()
+        };
+        assert!(!unchanged.changes_timelocked_fields(&state));
+
+        // fields that are not timelocked can be changed directly
+        let input = StateInput {
+            recovered_margin: Some(state.recovered_margin + 1),
+            marinade_minted_gsol: Some(0),
+            state_update_delay: Some(state.state_update_delay),
+            ..unchanged.clone()
+        };
+        assert!(!input.changes_timelocked_fields(&state));
+
+        let changes = [
+            StateInput {
+                treasury: Pubkey::new_unique(),
+                ..unchanged.clone()
+            },
+            StateInput {
+                marinade_state: Pubkey::new_unique(),
+                ..unchanged.clone()
+            },
+            StateInput {
+                blaze_state: Pubkey::new_unique(),
+                ..unchanged.clone()
+            },
+            StateInput {
+                liq_pool_proportion: state.liq_pool_proportion + 1,
+                ..unchanged.clone()
+            },
+            StateInput {
+                liq_pool_min_proportion: state.liq_pool_min_proportion + 1,
+                ..unchanged.clone()
+            },
+            StateInput {
+                state_update_delay: Some(0),
+                ..unchanged.clone()
+            },
+            StateInput {
+                gsol_mint_authority_bump: state.gsol_mint_authority_bump.wrapping_add(1),
+                ..unchanged.clone()
+            },
+            StateInput {
+                msol_authority_bump: state.msol_authority_bump.wrapping_add(1),
+                ..unchanged.clone()
+            },
+            StateInput {
+                bsol_authority_bump: state.bsol_authority_bump.wrapping_add(1),
+                ..unchanged.clone()
+            },
+        ];
+        for input in changes {
+            assert!(input.changes_timelocked_fields(&state));
+        }
+    }
+
+    #[test]
+    fn test_set_timelocked_values() {
+        let mut state = fixture_state();
+        let input = StateInput {
+            marinade_state: state.marinade_state,
+            blaze_state: state.blaze_state,
+            update_authority: state.update_authority,
+            treasury: Pubkey::new_unique(),
+            gsol_mint_authority_bump: state.gsol_mint_authority_bump,
+            msol_authority_bump: state.msol_authority_bump,
+            bsol_authority_bump: state.bsol_authority_bump,
+            liq_pool_proportion: state.liq_pool_proportion,
+            liq_pool_min_proportion: state.liq_pool_min_proportion,
+            ..state_input()
+        };
+        assert!(!input.sets_untimelocked_fields());
+        let queued = TimelockedStateInput::new(&input, &state);
+        assert_eq!(queued.state_update_delay, state.state_update_delay);
+
+        // changes made while the update is pending are kept
+        state.marinade_minted_gsol += 1_000;
+        state.max_total_gsol_supply = 5_000;
+        let before = state.clone();
+
+        state.set_timelocked_values(&queued);
+        assert_eq!(state.treasury, input.treasury);
+        assert_eq!(state.marinade_minted_gsol, before.marinade_minted_gsol);
+        assert_eq!(state.max_total_gsol_supply, before.max_total_gsol_supply);
+
+        let input = StateInput {
+            max_total_gsol_supply: Some(0),
+            ..input
+        };
+        assert!(input.sets_untimelocked_fields());
+    }
+
+    fn empty_report() -> EpochReportAccount {
+        EpochReportAccount {
+            state_address: Pubkey::default(),
+            epoch: 10,
+            tickets: 0,
+            total_ordered_lamports: 0,
+            extractable_yield: 0,
+            extracted_yield: 0,
+            current_gsol_supply: 0,
+            bump: 0,
+            treasury_tickets: 0,
+            treasury_ordered_lamports: 0,
+            deposit_epoch: 0,
+            deposited_lamports: 0,
+        }
+    }
+
+    #[test]
+    fn test_settle_treasury_tickets() {
+        let mut report = empty_report();
+        report.extractable_yield = 500;
+        report.extracted_yield = 1_000;
+        report.treasury_tickets = 1;
+        report.treasury_ordered_lamports = 200;
+
+        // ordered yield is not extracted yet, but still counts as accrued
+        assert_eq!(report.all_extractable_yield(), 1_700);
+
+        report.settle_treasury_tickets();
+        assert_eq!(report.extracted_yield, 1_200);
+        assert_eq!(report.treasury_tickets, 0);
+        assert_eq!(report.treasury_ordered_lamports, 0);
+        assert_eq!(report.all_extractable_yield(), 1_700);
+    }
+
+    #[test]
+    fn test_add_deposit() {
+        let mut report = empty_report();
+
+        assert_eq!(report.add_deposit(3_000, 10).unwrap(), 3_000);
+        assert_eq!(report.add_deposit(2_000, 10).unwrap(), 5_000);
+        assert_eq!(report.deposit_epoch, 10);
+
+        // the count starts afresh in the next epoch, even if the report has not been updated
+        assert_eq!(report.add_deposit(1_000, 11).unwrap(), 1_000);
+        assert_eq!(report.deposit_epoch, 11);
+        assert_eq!(report.deposited_lamports, 1_000);
+        assert_eq!(report.epoch, 10);
+
+        // the report's entry in the history only keeps deposits made in its own epoch
+        let entry = EpochReportHistoryEntry::from(&report);
+        assert_eq!(entry.epoch, 10);
+        assert_eq!(entry.deposited_lamports, 0);
+
+        report.epoch = 11;
+        let entry = EpochReportHistoryEntry::from(&report);
+        assert_eq!(entry.deposited_lamports, 1_000);
+    }
+
+    #[test]
+    fn test_check_deposit_caps_without_caps() {
+        let state = fixture_state();
+
+        state
+            .check_deposit_caps(1_000, u64::MAX - 1_000, u64::MAX)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_check_deposit_caps_supply_cap() {
+        let mut state = fixture_state();
+        state.max_total_gsol_supply = 10_000;
+
+        state.check_deposit_caps(1_000, 9_000, 0).unwrap();
+        assert!(state.check_deposit_caps(1_001, 9_000, 0).is_err());
+        assert!(state.check_deposit_caps(1, u64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn test_check_deposit_caps_epoch_limit() {
+        let mut state = fixture_state();
+        state.max_deposit_per_epoch = 5_000;
+
+        state.check_deposit_caps(2_000, 0, 5_000).unwrap();
+        assert!(state.check_deposit_caps(1, 0, 5_001).is_err());
+    }
+
+    fn gsol_mint(supply: u64) -> Mint {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::Mint as SplMint;
+
+        let mint = SplMint {
+            supply,
+            is_initialized: true,
+            ..Default::default()
+        };
+        let mut data = vec![0; SplMint::LEN];
+        SplMint::pack(mint, &mut data).unwrap();
+        Mint::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn test_record_deposit_without_epoch_report() {
+        let mut state = fixture_state();
+        state.max_total_gsol_supply = 10_000;
+        state
+            .record_deposit(None, &gsol_mint(9_000), 1_000)
+            .unwrap();
+        assert!(state
+            .record_deposit(None, &gsol_mint(9_000), 1_001)
+            .is_err());
+
+        // deposits can't be counted per epoch without the report
+        state.max_deposit_per_epoch = 5_000;
+        assert!(state.record_deposit(None, &gsol_mint(0), 1).is_err());
+    }
+
+    #[test]
+    fn test_deposit_route() {
+        let mut state = fixture_state();
+        // roughly 86% of the gSOL is backed by marinade
+        assert!(state.marinade_minted_gsol > 5 * state.blaze_minted_gsol);
+
+        // no target set
+        assert!(state.deposit_route(true, true).is_err());
+
+        state.marinade_target_bps = 5_000;
+        state.blaze_target_bps = 5_000;
+        assert_eq!(state.deposit_route(true, true).unwrap(), DepositPool::Blaze);
+
+        // a disabled pool receives no deposits, even when below target
+        assert_eq!(
+            state.deposit_route(true, false).unwrap(),
+            DepositPool::Marinade
+        );
+        assert_eq!(
+            state.deposit_route(false, true).unwrap(),
+            DepositPool::Blaze
+        );
+        assert!(state.deposit_route(false, false).is_err());
+
+        state.marinade_target_bps = 9_000;
+        state.blaze_target_bps = 1_000;
+        assert_eq!(
+            state.deposit_route(true, true).unwrap(),
+            DepositPool::Marinade
+        );
+
+        // exactly on target, the pool with the larger target is chosen
+        state.marinade_minted_gsol = 7_000;
+        state.blaze_minted_gsol = 3_000;
+        state.marinade_target_bps = 7_000;
+        state.blaze_target_bps = 3_000;
+        assert_eq!(
+            state.deposit_route(true, true).unwrap(),
+            DepositPool::Marinade
+        );
+
+        // nothing deposited yet
+        state.marinade_minted_gsol = 0;
+        state.blaze_minted_gsol = 0;
+        state.marinade_target_bps = 0;
+        state.blaze_target_bps = 10_000;
+        assert_eq!(state.deposit_route(true, true).unwrap(), DepositPool::Blaze);
+
+        // no overflow with large balances
+        state.marinade_minted_gsol = u64::MAX;
+        state.blaze_minted_gsol = u64::MAX;
+        state.marinade_target_bps = 5_001;
+        state.blaze_target_bps = 4_999;
+        assert_eq!(
+            state.deposit_route(true, true).unwrap(),
+            DepositPool::Marinade
+        );
+    }
+
+    /// Check the values carried over from the mainnet state fixture
+    fn assert_mainnet_state_values(state: &State) {
+        assert_eq!(
+            state.marinade_state,
+            pubkey!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC")
+        );
+        assert_eq!(
+            state.update_authority,
+            pubkey!("48V9nmW9awiR9BmihdGhUL3ZpYJ8MCgGeUoSWbtqjicv")
+        );
+        assert_eq!(
+            state.gsol_mint,
+            pubkey!("gso1xA56hacfgTHTF4F7wN5r4jbnJsKh99vR595uybA")
+        );
+        assert_eq!(
+            state.treasury,
+            pubkey!("6HQrvpMJFqMj35JqMReyhnUrRXNucAAB6FywdDu7xPKA")
+        );
+        assert_eq!(
+            state.blaze_state,
+            pubkey!("stk9ApL5HeVAwPLr3TLhDXdZS8ptVu7zp6ov8HFDuMi")
+        );
+        assert_eq!(state.gsol_mint_authority_bump, 255);
+        assert_eq!(state.msol_authority_bump, 255);
+        assert_eq!(state.bsol_authority_bump, 255);
+        assert_eq!(state.liq_pool_proportion, 10);
+        assert_eq!(state.liq_pool_min_proportion, 5);
+        assert_eq!(state.marinade_minted_gsol, 978_350_187_319);
+        assert_eq!(state.blaze_minted_gsol, 164_920_189_999);
+    }
+
+    #[test]
+    fn test_migrate_mainnet_state() {
+        let legacy_data = load_account_data(include_str!(
+            "../../../packages/tests/fixtures/sunrise_state_v0.json"
+        ));
+
+        // before migration, the legacy account cannot be read as State
+        assert!(State::try_deserialize(&mut &legacy_data[..]).is_err());
+
+        let mut state = State::try_deserialize_any_version(&legacy_data).unwrap();
+        assert_eq!(state.version, 0);
+        assert_mainnet_state_values(&state);
+
+        state.migrate();
+        let mut migrated_data = vec![0; State::SPACE];
+        state.try_serialize(&mut &mut migrated_data[..]).unwrap();
+
+        // the legacy data is preserved as a prefix of the migrated account
+        assert_eq!(&migrated_data[..legacy_data.len()], &legacy_data[..]);
+
+        let migrated = State::try_deserialize(&mut &migrated_data[..]).unwrap();
+        assert_mainnet_state_values(&migrated);
+        assert_eq!(migrated.version, State::CURRENT_VERSION);
+        assert_eq!(
+            migrated.estimated_unstake_fee_bps,
+            State::DEFAULT_ESTIMATED_UNSTAKE_FEE_BPS
+        );
+        assert_eq!(migrated.recovered_margin, State::DEFAULT_RECOVERED_MARGIN);
+        assert_eq!(
+            migrated.state_update_delay,
+            State::DEFAULT_STATE_UPDATE_DELAY
+        );
+    }
+
+    #[test]
+    fn test_migrated_state_fixture() {
+        let data = load_account_data(include_str!(
+            "../../../packages/tests/fixtures/scenarios/sunrise_state.json"
+        ));
+        assert_eq!(data.len(), State::SPACE);
+
+        let mut state = State::try_deserialize(&mut &data[..]).unwrap();
+        assert_mainnet_state_values(&state);
+
+        assert_eq!(state.version, State::CURRENT_VERSION);
+
+        // migrating an up-to-date account changes nothing
+        state.migrate();
+        let mut migrated_data = vec![0; State::SPACE];
+        state.try_serialize(&mut &mut migrated_data[..]).unwrap();
+        assert_eq!(migrated_data, data);
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
+    adapters::{SplStakePoolAdapter, StakePoolAdapter},
+    events::DepositEvent,
+    state::{operation, EpochReportAccount, PoolEntry, PoolRegistry, PoolType},
+    sunrise_spl::{is_deposit_token_account, is_spl_pool, minted_gsol_counter, PoolAuthoritySeeds},
     utils::{seeds, token as TokenUtils},
-    State,
+    ErrorCode, State,
 };
 use anchor_lang::{
     prelude::*,
@@ -27,12 +31,13 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 ///   9. `[]` Token program id
 ///  10. `[s]` (Optional) Stake pool sol deposit authority.
 
+#[event_cpi]
 #[derive(Accounts, Clone)]
 pub struct SplDepositSol<'info> {
     #[account(
         mut,
         has_one = gsol_mint,
-        constraint = state.blaze_state == *stake_pool.key
+        constraint = is_spl_pool(&state, &pool_entry, stake_pool.key) @ ErrorCode::PoolNotRegistered,
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
@@ -51,11 +56,17 @@ pub struct SplDepositSol<'info> {
     )]
     pub depositor_gsol_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut, token::authority = bsol_account_authority)]
+    #[account(
+        mut,
+        token::authority = bsol_account_authority,
+        constraint = is_deposit_token_account(&state, &pool_entry, &bsol_token_account.key())?
+            @ ErrorCode::InvalidPoolTokenAccount
+    )]
     pub bsol_token_account: Account<'info, TokenAccount>,
     #[account(
-        seeds = [state.key().as_ref(), seeds::BSOL_ACCOUNT],
-        bump = state.bsol_authority_bump
+        constraint = *bsol_account_authority.key
+            == PoolAuthoritySeeds::new(&state, &pool_entry).address()?
+            @ ErrorCode::InvalidPoolAuthority
     )]
     /// CHECK: Checked by CPI to Spl Stake Program
     pub bsol_account_authority: AccountInfo<'info>,
@@ -63,21 +74,42 @@ pub struct SplDepositSol<'info> {
     #[account(mut)]
     /// CHECK: Checked by CPI to Spl Stake Program
     pub stake_pool: AccountInfo<'info>,
-    /// CHECK: Checked by CPI to Spl Stake Program
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), seeds::POOL_ENTRY, stake_pool.key.as_ref()],
+        bump = pool_entry.bump,
+    )]
+    /// The pool's entry, if the pool is not Blaze
+    pub pool_entry: Option<Box<Account<'info, PoolEntry>>>,
+    /// CHECK: Checked against the stake pool
     pub stake_pool_withdraw_authority: AccountInfo<'info>,
     /// CHECK: Checked by CPI to Spl Stake Program
     #[account(mut)]
-    /// CHECK: Checked by CPI to Spl Stake Program
+    /// CHECK: Checked against the stake pool
     pub reserve_stake_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK: Checked by CPI to Spl Stake Program
+    /// CHECK: Checked against the stake pool
     pub manager_fee_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK: Checked by CPI to Spl Stake Program
+    /// CHECK: Checked against the stake pool
     pub stake_pool_token_mint: AccountInfo<'info>,
     /// CHECK:
     pub stake_pool_program: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), seeds::EPOCH_REPORT_ACCOUNT],
+        bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Option<Box<Account<'info, EpochReportAccount>>>,
+
+    /// Required once the registry has been created, see State::check_accepts_deposits
+    #[account(
+        seeds = [state.key().as_ref(), seeds::POOL_REGISTRY],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Option<Box<Account<'info, PoolRegistry>>>,
+
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
@@ -91,57 +123,47 @@ impl<'info> SplDepositSol<'info> {
         Ok(())
     }
 
-    pub fn deposit_sol(&mut self, amount: u64) -> Result<()> {
+    fn deposit_event(&self, lamports: u64, gsol_minted: u64) -> DepositEvent {
+        DepositEvent {
+            state: self.state.key(),
+            depositor: self.depositor.key(),
+            pool: self.stake_pool.key(),
+            lamports,
+            gsol_minted,
+        }
+    }
+
+    /// Deposit SOL, failing if less than `min_gsol_out` gSOL is minted to the depositor.
+    /// Returns the amount of gSOL minted.
+    pub fn deposit_sol_with_min_out(&mut self, amount: u64, min_gsol_out: u64) -> Result<u64> {
+        let gsol_balance_before = self.depositor_gsol_token_account.amount;
+        self.deposit_sol(amount)?;
+
+        self.depositor_gsol_token_account.reload()?;
+        let gsol_out = self
+            .depositor_gsol_token_account
+            .amount
+            .checked_sub(gsol_balance_before)
+            .ok_or(crate::ErrorCode::InvalidCalculation)?;
+        require_gte!(gsol_out, min_gsol_out, crate::ErrorCode::MinGsolOutNotMet);
+
+        Ok(gsol_out)
+    }
+
+    /// Deposit SOL and mint gSOL to the value of the bSOL received. Returns the amount of gSOL minted.
+    pub fn deposit_sol(&mut self, amount: u64) -> Result<u64> {
         self.check_stake_pool_program()?;
+        self.state.check_accepts_deposits(
+            self.pool_registry.as_deref().map(|registry| &**registry),
+            self.stake_pool.key,
+            PoolType::Spl,
+        )?;
 
         // Get the bSOL balance before deposit
         let bsol_balance_before = self.bsol_token_account.amount;
 
-        // Build instruction data with discriminator 14 for depositSol
-        let mut data = vec![14u8];
-        data.extend_from_slice(&amount.to_le_bytes());
-
-        // Build accounts list
-        let accounts = vec![
-            AccountMeta::new(*self.stake_pool.key, false),
-            AccountMeta::new_readonly(*self.stake_pool_withdraw_authority.key, false),
-            AccountMeta::new(*self.reserve_stake_account.key, false),
-            AccountMeta::new(*self.depositor.key, true),
-            AccountMeta::new(self.bsol_token_account.key(), false),
-            AccountMeta::new(*self.manager_fee_account.key, false),
-            AccountMeta::new(*self.manager_fee_account.key, false), // referral fee account
-            AccountMeta::new(*self.stake_pool_token_mint.key, false),
-            AccountMeta::new_readonly(self.system_program.key(), false),
-            AccountMeta::new_readonly(self.token_program.key(), false),
-            // AccountMeta::new_readonly(*self.bsol_account_authority.key, true), // sol deposit authority
-        ];
-
-        let instruction = Instruction {
-            program_id: SPL_STAKE_POOL_ID,
-            accounts,
-            data,
-        };
-
-        let bump = self.state.bsol_authority_bump;
-        let state_key = self.state.to_account_info().key;
-        let seeds = [state_key.as_ref(), seeds::BSOL_ACCOUNT, &[bump]];
-
-        invoke_signed(
-            &instruction,
-            &[
-                self.stake_pool.clone(),
-                self.stake_pool_withdraw_authority.clone(),
-                self.reserve_stake_account.clone(),
-                self.depositor.to_account_info(),
-                self.bsol_token_account.to_account_info(),
-                self.manager_fee_account.clone(),
-                self.stake_pool_token_mint.clone(),
-                self.system_program.to_account_info(),
-                self.token_program.to_account_info(),
-                // self.bsol_account_authority.clone(),
-            ],
-            &[&seeds],
-        )?;
+        let properties = (&*self).into();
+        SplStakePoolAdapter::load(&self.stake_pool)?.deposit_sol(&properties, amount)?;
 
         // Reload the bSOL token account to get the updated balance
         self.bsol_token_account.reload()?;
@@ -152,10 +174,9 @@ impl<'info> SplDepositSol<'info> {
             .checked_sub(bsol_balance_before)
             .ok_or(crate::ErrorCode::InvalidCalculation)?;
 
-        // Deserialize the stake pool to get the exchange rate
+        // Convert bSOL tokens to their SOL value at the post-deposit exchange rate.
+        // The deposit succeeded, so the pool is up to date for this epoch.
         let stake_pool = crate::utils::spl::deserialize_spl_stake_pool(&self.stake_pool)?;
-
-        // Convert bSOL tokens to their SOL value
         let sol_value =
             crate::utils::spl::calc_lamports_from_bsol_amount(&stake_pool, actual_bsol_received)?;
 
@@ -169,9 +190,138 @@ impl<'info> SplDepositSol<'info> {
             &self.state,
         )?;
 
-        let state = &mut self.state;
-        self.state.blaze_minted_gsol = state.blaze_minted_gsol.checked_add(sol_value).unwrap();
+        let minted_gsol = minted_gsol_counter(&mut self.state, &mut self.pool_entry);
+        *minted_gsol = minted_gsol.checked_add(sol_value).unwrap();
 
-        Ok(())
+        Ok(sol_value)
+    }
+}
+
+/// The accounts needed to deposit SOL into an SPL pool on behalf of this sunrise instance,
+/// crediting the pool tokens to the bsol token account.
+pub struct SplDepositSolProperties<'info> {
+    pub authority_seeds: PoolAuthoritySeeds,
+    /// Provides the lamports to be deposited
+    pub depositor: AccountInfo<'info>,
+    pub bsol_token_account: AccountInfo<'info>,
+    pub stake_pool: AccountInfo<'info>,
+    pub stake_pool_withdraw_authority: AccountInfo<'info>,
+    pub reserve_stake_account: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub stake_pool_token_mint: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+impl<'a> From<SplDepositSol<'a>> for SplDepositSolProperties<'a> {
+    fn from(deposit: SplDepositSol<'a>) -> Self {
+        Self {
+            authority_seeds: PoolAuthoritySeeds::new(&deposit.state, &deposit.pool_entry),
+            depositor: deposit.depositor.to_account_info(),
+            bsol_token_account: deposit.bsol_token_account.to_account_info(),
+            stake_pool: deposit.stake_pool,
+            stake_pool_withdraw_authority: deposit.stake_pool_withdraw_authority,
+            reserve_stake_account: deposit.reserve_stake_account,
+            manager_fee_account: deposit.manager_fee_account,
+            stake_pool_token_mint: deposit.stake_pool_token_mint,
+            system_program: deposit.system_program.to_account_info(),
+            token_program: deposit.token_program.to_account_info(),
+        }
+    }
+}
+impl<'a> From<&SplDepositSol<'a>> for SplDepositSolProperties<'a> {
+    fn from(deposit: &SplDepositSol<'a>) -> Self {
+        deposit.to_owned().into()
     }
 }
+
+/// Deposit `lamports` from the depositor into the pool reserve.
+/// Does not touch any gSOL accounting.
+pub fn deposit_sol_to_pool(properties: &SplDepositSolProperties, lamports: u64) -> Result<()> {
+    let stake_pool = crate::utils::spl::deserialize_spl_stake_pool(&properties.stake_pool)?;
+    stake_pool.check_accounts(
+        properties.stake_pool.key,
+        properties.stake_pool_token_mint.key,
+        properties.manager_fee_account.key,
+        properties.stake_pool_withdraw_authority.key,
+    )?;
+    stake_pool.check_reserve_stake(properties.reserve_stake_account.key)?;
+
+    // Build instruction data with discriminator 14 for depositSol
+    let mut data = vec![14u8];
+    data.extend_from_slice(&lamports.to_le_bytes());
+
+    // Build accounts list
+    let accounts = vec![
+        AccountMeta::new(*properties.stake_pool.key, false),
+        AccountMeta::new_readonly(*properties.stake_pool_withdraw_authority.key, false),
+        AccountMeta::new(*properties.reserve_stake_account.key, false),
+        AccountMeta::new(*properties.depositor.key, true),
+        AccountMeta::new(*properties.bsol_token_account.key, false),
+        AccountMeta::new(*properties.manager_fee_account.key, false),
+        AccountMeta::new(*properties.manager_fee_account.key, false), // referral fee account
+        AccountMeta::new(*properties.stake_pool_token_mint.key, false),
+        AccountMeta::new_readonly(*properties.system_program.key, false),
+        AccountMeta::new_readonly(*properties.token_program.key, false),
+        // AccountMeta::new_readonly(*properties.bsol_account_authority.key, true), // sol deposit authority
+    ];
+
+    let instruction = Instruction {
+        program_id: SPL_STAKE_POOL_ID,
+        accounts,
+        data,
+    };
+
+    let seeds = properties.authority_seeds.seeds();
+
+    invoke_signed(
+        &instruction,
+        &[
+            properties.stake_pool.clone(),
+            properties.stake_pool_withdraw_authority.clone(),
+            properties.reserve_stake_account.clone(),
+            properties.depositor.clone(),
+            properties.bsol_token_account.clone(),
+            properties.manager_fee_account.clone(),
+            properties.stake_pool_token_mint.clone(),
+            properties.system_program.clone(),
+            properties.token_program.clone(),
+        ],
+        &[&seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn spl_deposit_sol_handler(ctx: Context<SplDepositSol>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::SPL_DEPOSIT)?;
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        amount,
+    )?;
+    let gsol_minted = ctx.accounts.deposit_sol(amount)?;
+    emit_cpi!(ctx.accounts.deposit_event(amount, gsol_minted));
+    Ok(())
+}
+
+pub fn spl_deposit_sol_v2_handler(
+    ctx: Context<SplDepositSol>,
+    amount: u64,
+    min_gsol_out: u64,
+) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::SPL_DEPOSIT)?;
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        amount,
+    )?;
+    let gsol_minted = ctx
+        .accounts
+        .deposit_sol_with_min_out(amount, min_gsol_out)?;
+    emit_cpi!(ctx.accounts.deposit_event(amount, gsol_minted));
+    Ok(())
+}
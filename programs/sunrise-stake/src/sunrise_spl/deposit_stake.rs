@@ -1,6 +1,9 @@
 use crate::{
+    events::DepositEvent,
+    state::{operation, EpochReportAccount, PoolEntry, PoolRegistry, PoolType},
+    sunrise_spl::{is_deposit_token_account, is_spl_pool, minted_gsol_counter, PoolAuthoritySeeds},
     utils::{seeds, token as TokenUtils},
-    State,
+    ErrorCode, State,
 };
 use anchor_lang::solana_program::stake::state::StakeStateV2;
 use anchor_lang::{
@@ -32,12 +35,13 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 ///   12. '[]' Sysvar stake history account
 ///   13. `[]` Pool token program id,
 ///   14. `[]` Stake program id,
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SplDepositStake<'info> {
     #[account(
         mut,
         has_one = gsol_mint,
-        constraint = state.blaze_state == *stake_pool.key
+        constraint = is_spl_pool(&state, &pool_entry, stake_pool.key) @ ErrorCode::PoolNotRegistered,
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
@@ -59,11 +63,17 @@ pub struct SplDepositStake<'info> {
     )]
     pub depositor_gsol_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut, token::authority = bsol_account_authority)]
+    #[account(
+        mut,
+        token::authority = bsol_account_authority,
+        constraint = is_deposit_token_account(&state, &pool_entry, &bsol_token_account.key())?
+            @ ErrorCode::InvalidPoolTokenAccount
+    )]
     pub bsol_token_account: Account<'info, TokenAccount>,
     #[account(
-        seeds = [state.key().as_ref(), seeds::BSOL_ACCOUNT],
-        bump = state.bsol_authority_bump
+        constraint = *bsol_account_authority.key
+            == PoolAuthoritySeeds::new(&state, &pool_entry).address()?
+            @ ErrorCode::InvalidPoolAuthority
     )]
     /// CHECK:
     pub bsol_account_authority: AccountInfo<'info>,
@@ -71,26 +81,33 @@ pub struct SplDepositStake<'info> {
     #[account(mut)]
     /// CHECK: Checked by seeds of pool_details
     pub stake_pool: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), seeds::POOL_ENTRY, stake_pool.key.as_ref()],
+        bump = pool_entry.bump,
+    )]
+    /// The pool's entry, if the pool is not Blaze
+    pub pool_entry: Option<Box<Account<'info, PoolEntry>>>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub validator_list: AccountInfo<'info>,
     #[account(signer)]
     /// CHECK:
     pub stake_pool_deposit_authority: AccountInfo<'info>,
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub stake_pool_withdraw_authority: AccountInfo<'info>,
     #[account(mut)]
     /// CHECK:
     pub validator_stake_account: AccountInfo<'info>,
     /// CHECK:
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub reserve_stake_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub manager_fee_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub stake_pool_token_mint: AccountInfo<'info>,
     /// CHECK:
     pub sysvar_stake_history: AccountInfo<'info>,
@@ -101,6 +118,20 @@ pub struct SplDepositStake<'info> {
     /// CHECK:
     pub stake_pool_program: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), seeds::EPOCH_REPORT_ACCOUNT],
+        bump = epoch_report_account.bump,
+    )]
+    pub epoch_report_account: Option<Box<Account<'info, EpochReportAccount>>>,
+
+    /// Required once the registry has been created, see State::check_accepts_deposits
+    #[account(
+        seeds = [state.key().as_ref(), seeds::POOL_REGISTRY],
+        bump = pool_registry.bump,
+    )]
+    pub pool_registry: Option<Box<Account<'info, PoolRegistry>>>,
+
     pub token_program: Program<'info, Token>,
 }
 
@@ -113,8 +144,25 @@ impl SplDepositStake<'_> {
         Ok(())
     }
 
-    pub fn deposit_stake(&mut self) -> Result<()> {
+    /// Deposit the stake account and mint gSOL to the value of the bSOL received.
+    /// Returns the amount of gSOL minted.
+    pub fn deposit_stake(&mut self) -> Result<u64> {
         self.check_stake_pool_program()?;
+        self.state.check_accepts_deposits(
+            self.pool_registry.as_deref().map(|registry| &**registry),
+            self.stake_pool.key,
+            PoolType::Spl,
+        )?;
+
+        let stake_pool = crate::utils::spl::deserialize_spl_stake_pool(&self.stake_pool)?;
+        stake_pool.check_accounts(
+            self.stake_pool.key,
+            self.stake_pool_token_mint.key,
+            self.manager_fee_account.key,
+            self.stake_pool_withdraw_authority.key,
+        )?;
+        stake_pool.check_reserve_stake(self.reserve_stake_account.key)?;
+        stake_pool.check_validator_list(self.validator_list.key)?;
 
         let stake_account_info =
             borsh1::try_from_slice_unchecked::<StakeStateV2>(&self.stake_account.data.borrow())?;
@@ -126,9 +174,8 @@ impl SplDepositStake<'_> {
         // Get the bSOL balance before deposit
         let bsol_balance_before = self.bsol_token_account.amount;
 
-        let bump = self.state.bsol_authority_bump;
-        let state_key = self.state.to_account_info().key;
-        let seeds = [state_key.as_ref(), seeds::BSOL_ACCOUNT, &[bump]];
+        let authority_seeds = PoolAuthoritySeeds::new(&self.state, &self.pool_entry);
+        let seeds = authority_seeds.seeds();
 
         // Build instruction data with discriminator 11 for depositStake
         let data = vec![11u8];
@@ -205,9 +252,31 @@ impl SplDepositStake<'_> {
             &self.state,
         )?;
 
-        let state = &mut self.state;
-        self.state.blaze_minted_gsol = state.blaze_minted_gsol.checked_add(sol_value).unwrap();
+        let minted_gsol = minted_gsol_counter(&mut self.state, &mut self.pool_entry);
+        *minted_gsol = minted_gsol.checked_add(sol_value).unwrap();
 
-        Ok(())
+        Ok(sol_value)
     }
 }
+
+pub fn spl_deposit_stake_handler(ctx: Context<SplDepositStake>) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::SPL_DEPOSIT)?;
+    let lamports = ctx.accounts.stake_account.lamports();
+    ctx.accounts.state.record_deposit(
+        ctx.accounts.epoch_report_account.as_deref_mut(),
+        &ctx.accounts.gsol_mint,
+        lamports,
+    )?;
+    let gsol_minted = ctx.accounts.deposit_stake()?;
+
+    emit_cpi!(DepositEvent {
+        state: ctx.accounts.state.key(),
+        depositor: ctx.accounts.stake_account_depositor.key(),
+        pool: ctx.accounts.stake_pool.key(),
+        lamports,
+        gsol_minted,
+    });
+    Ok(())
+}
@@ -1,9 +1,13 @@
 pub mod deposit_sol;
 pub mod deposit_stake;
+pub mod order_unstake;
+pub mod pool;
 pub mod withdraw_sol;
 pub mod withdraw_stake;
 
 pub use deposit_sol::*;
 pub use deposit_stake::*;
+pub use order_unstake::*;
+pub use pool::*;
 pub use withdraw_sol::*;
 pub use withdraw_stake::*;
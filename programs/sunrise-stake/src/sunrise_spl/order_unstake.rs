@@ -0,0 +1,188 @@
+use crate::{
+    adapters::{SplStakePoolAdapter, StakePoolAdapter},
+    events::OrderUnstakeEvent,
+    state::{operation, PoolEntry, SplUnstakeTicketAccount, State},
+    sunrise_spl::{is_spl_pool, minted_gsol_counter, PoolAuthoritySeeds},
+    utils::{seeds, spl, token as TokenUtils},
+    ErrorCode, SplWithdrawStakeProperties,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Delayed unstake through the Blaze pool.
+///
+/// Burns the user's gSOL, withdraws the equivalent value from the Blaze pool as a
+/// stake account owned by the user, and deactivates it. Once the deactivation epoch
+/// has passed, the user can withdraw the SOL from the stake account directly
+/// via the native stake program.
+/// A ticket account records the withdrawal, analogous to SunriseTicketAccount for Marinade.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SplOrderUnstake<'info> {
+    #[account(
+        mut,
+        has_one = gsol_mint,
+        constraint = is_spl_pool(&state, &pool_entry, stake_pool.key) @ ErrorCode::PoolNotRegistered
+    )]
+    pub state: Box<Account<'info, State>>,
+    #[account(mut)]
+    pub gsol_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = gsol_mint,
+        token::authority = user,
+    )]
+    pub user_gsol_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    /// CHECK: Should be an uninitialized stake account, checked by CPI to Spl Stake Program
+    pub user_new_stake_account: AccountInfo<'info>,
+
+    #[account(init, space = SplUnstakeTicketAccount::SPACE, payer = user)]
+    pub spl_ticket_account: Account<'info, SplUnstakeTicketAccount>,
+
+    #[account(mut, token::authority = bsol_account_authority)]
+    pub bsol_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = *bsol_account_authority.key
+            == PoolAuthoritySeeds::new(&state, &pool_entry).address()?
+            @ ErrorCode::InvalidPoolAuthority
+    )]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub bsol_account_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub stake_pool: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), seeds::POOL_ENTRY, stake_pool.key.as_ref()],
+        bump = pool_entry.bump,
+    )]
+    /// The pool's entry, if the pool is not Blaze
+    pub pool_entry: Option<Box<Account<'info, PoolEntry>>>,
+    /// CHECK: Checked against the stake pool
+    pub stake_pool_withdraw_authority: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: Checked against the stake pool
+    pub validator_stake_list: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub stake_account_to_split: AccountInfo<'info>, //either validator/reserve
+    #[account(mut)]
+    /// CHECK: Checked against the stake pool
+    pub manager_fee_account: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: Checked against the stake pool
+    pub stake_pool_token_mint: AccountInfo<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: Checked against the SPL stake pool program id
+    pub stake_pool_program: AccountInfo<'info>,
+    /// CHECK: Checked by CPI to Spl Stake Program
+    pub native_stake_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SplOrderUnstake<'info> {
+    fn check_stake_pool_program(&self) -> Result<()> {
+        require_keys_eq!(*self.stake_pool_program.key, spl::SPL_STAKE_POOL_PROGRAM_ID);
+        Ok(())
+    }
+
+    pub fn order_unstake(&mut self, lamports: u64) -> Result<()> {
+        self.check_stake_pool_program()?;
+
+        let properties = SplWithdrawStakeProperties {
+            authority_seeds: PoolAuthoritySeeds::new(&self.state, &self.pool_entry),
+            bsol_token_account: self.bsol_token_account.to_account_info(),
+            bsol_account_authority: self.bsol_account_authority.clone(),
+            stake_pool: self.stake_pool.clone(),
+            stake_pool_withdraw_authority: self.stake_pool_withdraw_authority.clone(),
+            validator_stake_list: self.validator_stake_list.clone(),
+            stake_account_to_split: self.stake_account_to_split.clone(),
+            new_stake_account: self.user_new_stake_account.clone(),
+            recipient: self.user.to_account_info(),
+            manager_fee_account: self.manager_fee_account.clone(),
+            stake_pool_token_mint: self.stake_pool_token_mint.clone(),
+            sysvar_clock: self.clock.to_account_info(),
+            native_stake_program: self.native_stake_program.clone(),
+            token_program: self.token_program.to_account_info(),
+        };
+        SplStakePoolAdapter::load(&self.stake_pool)?.delayed_unstake(&properties, lamports)?;
+
+        TokenUtils::burn(
+            lamports,
+            &self.gsol_mint.to_account_info(),
+            &self.user.to_account_info(),
+            &self.user_gsol_token_account.to_account_info(),
+            &self.token_program,
+        )?;
+
+        // Once yield has accrued the counter can be below the amount being unstaked,
+        // so clamp it as SplWithdrawSol::withdraw_sol does
+        let minted_gsol = minted_gsol_counter(&mut self.state, &mut self.pool_entry);
+        *minted_gsol = minted_gsol.saturating_sub(lamports);
+
+        let ticket = &mut self.spl_ticket_account;
+        ticket.state_address = self.state.key();
+        ticket.stake_account = self.user_new_stake_account.key();
+        ticket.beneficiary = self.user.key();
+        ticket.lamports = self.user_new_stake_account.lamports();
+        ticket.created_epoch = self.clock.epoch;
+
+        msg!(
+            "Stake account {} with {} lamports deactivating in epoch {}",
+            ticket.stake_account,
+            ticket.lamports,
+            ticket.created_epoch
+        );
+
+        Ok(())
+    }
+}
+
+pub fn spl_order_unstake_handler(ctx: Context<SplOrderUnstake>, lamports: u64) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::SPL_WITHDRAW)?;
+    ctx.accounts.order_unstake(lamports)?;
+
+    emit_cpi!(OrderUnstakeEvent {
+        state: ctx.accounts.state.key(),
+        staker: ctx.accounts.user.key(),
+        ticket: ctx.accounts.spl_ticket_account.key(),
+        gsol_burned: lamports,
+        lamports: ctx.accounts.spl_ticket_account.lamports,
+    });
+    Ok(())
+}
+
+/// Close a Blaze unstake ticket, returning its rent to the beneficiary.
+/// The ticket is a record only: the stake account is owned by the beneficiary
+/// regardless of whether the ticket exists.
+#[derive(Accounts)]
+pub struct SplCloseUnstakeTicket<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        close = beneficiary,
+        has_one = beneficiary,
+    )]
+    pub spl_ticket_account: Account<'info, SplUnstakeTicketAccount>,
+}
+
+pub fn spl_close_unstake_ticket_handler(ctx: Context<SplCloseUnstakeTicket>) -> Result<()> {
+    // The account is closed by the `close` constraint once the handler returns
+    msg!(
+        "Closing unstake ticket for stake account {}",
+        ctx.accounts.spl_ticket_account.stake_account
+    );
+    Ok(())
+}
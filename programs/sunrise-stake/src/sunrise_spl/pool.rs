@@ -0,0 +1,177 @@
+//! The SPL instructions act on Blaze when no pool entry is passed,
+//! and on the pool entry's pool otherwise.
+use crate::{
+    adapters::{SplStakePoolAdapter, StakePoolAdapter},
+    state::{PoolEntry, State},
+    utils::seeds::{BSOL_ACCOUNT, POOL_AUTHORITY},
+    ErrorCode,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{self, TokenAccount},
+};
+
+/// The seeds of the PDA holding this instance's tokens of an SPL pool.
+/// Blaze's is [state, BSOL_ACCOUNT], any other pool's is [state, POOL_AUTHORITY, pool].
+pub struct PoolAuthoritySeeds {
+    state: Pubkey,
+    pool: Option<Pubkey>,
+    bump: [u8; 1],
+}
+
+impl PoolAuthoritySeeds {
+    pub fn new(state: &Account<State>, pool_entry: &Option<Box<Account<PoolEntry>>>) -> Self {
+        match pool_entry {
+            Some(pool_entry) => Self::for_entry(state.key(), pool_entry),
+            None => Self {
+                state: state.key(),
+                pool: None,
+                bump: [state.bsol_authority_bump],
+            },
+        }
+    }
+
+    pub fn for_entry(state: Pubkey, pool_entry: &PoolEntry) -> Self {
+        Self {
+            state,
+            pool: Some(pool_entry.pool),
+            bump: [pool_entry.authority_bump],
+        }
+    }
+
+    pub fn seeds(&self) -> Vec<&[u8]> {
+        match &self.pool {
+            Some(pool) => vec![
+                self.state.as_ref(),
+                POOL_AUTHORITY,
+                pool.as_ref(),
+                &self.bump,
+            ],
+            None => vec![self.state.as_ref(), BSOL_ACCOUNT, &self.bump],
+        }
+    }
+
+    pub fn address(&self) -> Result<Pubkey> {
+        Pubkey::create_program_address(&self.seeds(), &crate::ID)
+            .map_err(|_| ErrorCode::InvalidPoolAuthority.into())
+    }
+}
+
+/// Whether `stake_pool` is Blaze, or the pool of the given pool entry.
+pub fn is_spl_pool(
+    state: &State,
+    pool_entry: &Option<Box<Account<PoolEntry>>>,
+    stake_pool: &Pubkey,
+) -> bool {
+    match pool_entry {
+        Some(pool_entry) => pool_entry.pool == *stake_pool,
+        None => state.blaze_state == *stake_pool,
+    }
+}
+
+/// The token account holding this instance's tokens of a pool with an entry:
+/// the associated token account of the pool authority PDA.
+pub fn pool_token_account(state: Pubkey, pool_entry: &PoolEntry) -> Result<Pubkey> {
+    let authority = PoolAuthoritySeeds::for_entry(state, pool_entry).address()?;
+    Ok(get_associated_token_address(
+        &authority,
+        &pool_entry.pool_mint,
+    ))
+}
+
+/// Whether `token_account` may receive deposits into the pool.
+/// Deposits into a pool with an entry must go to its pool token account,
+/// as that is the only one counted by value_pool_entries.
+pub fn is_deposit_token_account(
+    state: &Account<State>,
+    pool_entry: &Option<Box<Account<PoolEntry>>>,
+    token_account: &Pubkey,
+) -> Result<bool> {
+    match pool_entry {
+        Some(pool_entry) => Ok(*token_account == pool_token_account(state.key(), pool_entry)?),
+        None => Ok(true),
+    }
+}
+
+/// Value the holdings in every pool with an entry.
+/// The entries are passed as the last remaining accounts, as (pool entry, stake pool,
+/// pool token account) triples, one for each entry created by init_pool_entry,
+/// so that none can be left out. Disabled pools are valued too, as they still hold tokens.
+/// Returns the total value, and the remaining accounts before the entries.
+pub fn value_pool_entries<'a, 'info>(
+    state: &Account<State>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(u64, &'a [AccountInfo<'info>])> {
+    let entry_accounts = 3 * state.pool_entries as usize;
+    require_gte!(
+        remaining_accounts.len(),
+        entry_accounts,
+        ErrorCode::UnexpectedAccounts
+    );
+    let (leading_accounts, entries) =
+        remaining_accounts.split_at(remaining_accounts.len() - entry_accounts);
+
+    let mut valued_pools = Vec::with_capacity(state.pool_entries as usize);
+    let mut value: u64 = 0;
+    for entry in entries.chunks(3) {
+        let [pool_entry_info, stake_pool, token_account] = entry else {
+            unreachable!()
+        };
+        require_keys_eq!(
+            *pool_entry_info.owner,
+            crate::ID,
+            ErrorCode::UnexpectedAccounts
+        );
+        let pool_entry = PoolEntry::try_deserialize(&mut &pool_entry_info.data.borrow()[..])?;
+        require_keys_eq!(
+            pool_entry.state_address,
+            state.key(),
+            ErrorCode::UnexpectedAccounts
+        );
+        require!(
+            !valued_pools.contains(&pool_entry.pool),
+            ErrorCode::UnexpectedAccounts
+        );
+        valued_pools.push(pool_entry.pool);
+        require_keys_eq!(
+            *stake_pool.key,
+            pool_entry.pool,
+            ErrorCode::InvalidStakePoolAccount
+        );
+        require_keys_eq!(
+            *token_account.key,
+            pool_token_account(state.key(), &pool_entry)?,
+            ErrorCode::UnexpectedAccounts
+        );
+
+        // Nothing holds the pool tokens until the pool token account has been created
+        let pool_tokens = if token_account.data_is_empty() {
+            0
+        } else {
+            require_keys_eq!(
+                *token_account.owner,
+                token::ID,
+                ErrorCode::UnexpectedAccounts
+            );
+            TokenAccount::try_deserialize(&mut &token_account.data.borrow()[..])?.amount
+        };
+        let pool_value = SplStakePoolAdapter::load(stake_pool)?.value_of_holdings(pool_tokens)?;
+        value = value
+            .checked_add(pool_value)
+            .ok_or(ErrorCode::CalculationFailure)?;
+    }
+
+    Ok((value, leading_accounts))
+}
+
+/// The counter of gSOL minted against deposits into the pool
+pub fn minted_gsol_counter<'a>(
+    state: &'a mut State,
+    pool_entry: &'a mut Option<Box<Account<'_, PoolEntry>>>,
+) -> &'a mut u64 {
+    match pool_entry {
+        Some(pool_entry) => &mut pool_entry.minted_gsol,
+        None => &mut state.blaze_minted_gsol,
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    utils::{self, spl},
-    State,
+    adapters::{SplStakePoolAdapter, StakePoolAdapter},
+    events::LiquidUnstakeEvent,
+    state::{operation, PoolEntry},
+    sunrise_spl::{is_spl_pool, minted_gsol_counter, PoolAuthoritySeeds},
+    utils::{self, spl, token as TokenUtils},
+    ErrorCode, State,
 };
 use anchor_lang::{
     prelude::*,
@@ -30,12 +34,13 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 ///  11. `[]` Token program id
 ///  12. `[s]` (Optional) Stake pool sol withdraw authority
 
-#[derive(Accounts)]
+#[event_cpi]
+#[derive(Accounts, Clone)]
 pub struct SplWithdrawSol<'info> {
     #[account(
         mut,
         has_one = gsol_mint,
-        constraint = state.blaze_state == *stake_pool.key
+        constraint = is_spl_pool(&state, &pool_entry, stake_pool.key) @ ErrorCode::PoolNotRegistered
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
@@ -53,8 +58,9 @@ pub struct SplWithdrawSol<'info> {
     #[account(mut, token::authority = bsol_account_authority)]
     pub bsol_token_account: Account<'info, TokenAccount>,
     #[account(
-        seeds = [state.key().as_ref(), utils::seeds::BSOL_ACCOUNT],
-        bump = state.bsol_authority_bump
+        constraint = *bsol_account_authority.key
+            == PoolAuthoritySeeds::new(&state, &pool_entry).address()?
+            @ ErrorCode::InvalidPoolAuthority
     )]
     /// CHECK:
     pub bsol_account_authority: AccountInfo<'info>,
@@ -62,17 +68,24 @@ pub struct SplWithdrawSol<'info> {
     #[account(mut)]
     /// CHECK:
     pub stake_pool: AccountInfo<'info>,
-    /// CHECK:
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), utils::seeds::POOL_ENTRY, stake_pool.key.as_ref()],
+        bump = pool_entry.bump,
+    )]
+    /// The pool's entry, if the pool is not Blaze
+    pub pool_entry: Option<Box<Account<'info, PoolEntry>>>,
+    /// CHECK: Checked against the stake pool
     pub stake_pool_withdraw_authority: AccountInfo<'info>,
     /// CHECK:
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub reserve_stake_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub manager_fee_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub stake_pool_token_mint: AccountInfo<'info>,
     /// CHECK:
     pub sysvar_clock: AccountInfo<'info>,
@@ -88,77 +101,160 @@ pub struct SplWithdrawSol<'info> {
 const SPL_STAKE_POOL_ID: Pubkey =
     anchor_lang::solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
 
+/// The accounts needed to withdraw SOL from an SPL pool on behalf of this sunrise instance,
+/// burning pool tokens held by the pool authority PDA.
+pub struct SplWithdrawSolProperties<'info> {
+    pub authority_seeds: PoolAuthoritySeeds,
+    pub bsol_token_account: AccountInfo<'info>,
+    pub bsol_account_authority: AccountInfo<'info>,
+    pub stake_pool: AccountInfo<'info>,
+    pub stake_pool_withdraw_authority: AccountInfo<'info>,
+    pub reserve_stake_account: AccountInfo<'info>,
+    /// Receives the withdrawn lamports
+    pub recipient: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub stake_pool_token_mint: AccountInfo<'info>,
+    pub sysvar_clock: AccountInfo<'info>,
+    pub sysvar_stake_history: AccountInfo<'info>,
+    pub native_stake_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+impl<'a> From<SplWithdrawSol<'a>> for SplWithdrawSolProperties<'a> {
+    fn from(withdraw: SplWithdrawSol<'a>) -> Self {
+        Self {
+            authority_seeds: PoolAuthoritySeeds::new(&withdraw.state, &withdraw.pool_entry),
+            bsol_token_account: withdraw.bsol_token_account.to_account_info(),
+            bsol_account_authority: withdraw.bsol_account_authority,
+            stake_pool: withdraw.stake_pool,
+            stake_pool_withdraw_authority: withdraw.stake_pool_withdraw_authority,
+            reserve_stake_account: withdraw.reserve_stake_account,
+            recipient: withdraw.user.to_account_info(),
+            manager_fee_account: withdraw.manager_fee_account,
+            stake_pool_token_mint: withdraw.stake_pool_token_mint,
+            sysvar_clock: withdraw.sysvar_clock,
+            sysvar_stake_history: withdraw.sysvar_stake_history,
+            native_stake_program: withdraw.native_stake_program,
+            token_program: withdraw.token_program.to_account_info(),
+        }
+    }
+}
+impl<'a> From<&SplWithdrawSol<'a>> for SplWithdrawSolProperties<'a> {
+    fn from(withdraw: &SplWithdrawSol<'a>) -> Self {
+        withdraw.to_owned().into()
+    }
+}
+
+/// Withdraw `lamports` worth of bSOL from the pool reserve to the recipient.
+/// Does not touch any gSOL accounting.
+pub fn withdraw_sol_from_pool(properties: &SplWithdrawSolProperties, lamports: u64) -> Result<()> {
+    let seeds = properties.authority_seeds.seeds();
+
+    let stake_pool = spl::deserialize_spl_stake_pool(&properties.stake_pool)?;
+    stake_pool.check_accounts(
+        properties.stake_pool.key,
+        properties.stake_pool_token_mint.key,
+        properties.manager_fee_account.key,
+        properties.stake_pool_withdraw_authority.key,
+    )?;
+    stake_pool.check_reserve_stake(properties.reserve_stake_account.key)?;
+    let pool_tokens = spl::calc_bsol_from_lamports(&stake_pool, lamports)?;
+
+    // Build instruction data with discriminator 16 for withdrawSol
+    // WithdrawSol only takes pool_tokens amount as parameter
+    let mut data = vec![16u8];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    // Build accounts list
+    let accounts = vec![
+        AccountMeta::new(*properties.stake_pool.key, false),
+        AccountMeta::new_readonly(*properties.stake_pool_withdraw_authority.key, false),
+        AccountMeta::new_readonly(*properties.bsol_account_authority.key, true), // transfer authority
+        AccountMeta::new(*properties.bsol_token_account.key, false),             // burn pool tokens
+        AccountMeta::new(*properties.reserve_stake_account.key, false),
+        AccountMeta::new(*properties.recipient.key, false), // withdraw account
+        AccountMeta::new(*properties.manager_fee_account.key, false), // fee token account
+        AccountMeta::new(*properties.stake_pool_token_mint.key, false), // pool token mint
+        AccountMeta::new_readonly(*properties.sysvar_clock.key, false),
+        AccountMeta::new_readonly(*properties.sysvar_stake_history.key, false),
+        AccountMeta::new_readonly(*properties.native_stake_program.key, false),
+        AccountMeta::new_readonly(*properties.token_program.key, false),
+        AccountMeta::new_readonly(*properties.bsol_account_authority.key, true), // sol withdraw authority
+    ];
+
+    let instruction = Instruction {
+        program_id: SPL_STAKE_POOL_ID,
+        accounts,
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            properties.stake_pool.clone(),
+            properties.stake_pool_withdraw_authority.clone(),
+            properties.bsol_account_authority.clone(),
+            properties.bsol_token_account.clone(),
+            properties.reserve_stake_account.clone(),
+            properties.recipient.clone(),
+            properties.manager_fee_account.clone(),
+            properties.stake_pool_token_mint.clone(),
+            properties.sysvar_clock.clone(),
+            properties.sysvar_stake_history.clone(),
+            properties.native_stake_program.clone(),
+            properties.token_program.clone(),
+        ],
+        &[&seeds],
+    )?;
+
+    Ok(())
+}
+
 impl<'info> SplWithdrawSol<'info> {
     fn check_stake_pool_program(&self) -> Result<()> {
         require_keys_eq!(*self.stake_pool_program.key, SPL_STAKE_POOL_ID);
         Ok(())
     }
 
+    /// Withdraw `lamports` from the pool to the user and lower the minted gSOL counter.
+    /// The caller is responsible for burning the user's gSOL.
     pub fn withdraw_sol(&mut self, lamports: u64) -> Result<()> {
         self.check_stake_pool_program()?;
 
-        let bump = self.state.bsol_authority_bump;
-        let state_key = self.state.to_account_info().key;
-        let seeds = [state_key.as_ref(), utils::seeds::BSOL_ACCOUNT, &[bump]];
-
-        let stake_pool = spl::deserialize_spl_stake_pool(&self.stake_pool)?;
-        let pool_tokens = spl::calc_bsol_from_lamports(&stake_pool, lamports)?;
-
-        // Build instruction data with discriminator 16 for withdrawSol
-        // WithdrawSol only takes pool_tokens amount as parameter
-        let mut data = vec![16u8];
-        data.extend_from_slice(&pool_tokens.to_le_bytes());
-
-        // Build accounts list
-        let accounts = vec![
-            AccountMeta::new(*self.stake_pool.key, false),
-            AccountMeta::new_readonly(*self.stake_pool_withdraw_authority.key, false),
-            AccountMeta::new_readonly(*self.bsol_account_authority.key, true), // transfer authority
-            AccountMeta::new(self.bsol_token_account.key(), false),            // burn pool tokens
-            AccountMeta::new(*self.reserve_stake_account.key, false),
-            AccountMeta::new(*self.user.key, false), // withdraw account
-            AccountMeta::new(*self.manager_fee_account.key, false), // fee token account
-            AccountMeta::new(*self.stake_pool_token_mint.key, false), // pool token mint
-            AccountMeta::new_readonly(*self.sysvar_clock.key, false),
-            AccountMeta::new_readonly(*self.sysvar_stake_history.key, false),
-            AccountMeta::new_readonly(*self.native_stake_program.key, false),
-            AccountMeta::new_readonly(self.token_program.key(), false),
-            AccountMeta::new_readonly(*self.bsol_account_authority.key, true), // sol withdraw authority
-        ];
-
-        let instruction = Instruction {
-            program_id: SPL_STAKE_POOL_ID,
-            accounts,
-            data,
-        };
-
-        invoke_signed(
-            &instruction,
-            &[
-                self.stake_pool.clone(),
-                self.stake_pool_withdraw_authority.clone(),
-                self.bsol_account_authority.clone(),
-                self.bsol_token_account.to_account_info(),
-                self.reserve_stake_account.clone(),
-                self.user.to_account_info(),
-                self.manager_fee_account.clone(),
-                self.stake_pool_token_mint.clone(),
-                self.sysvar_clock.clone(),
-                self.sysvar_stake_history.clone(),
-                self.native_stake_program.clone(),
-                self.token_program.to_account_info(),
-            ],
-            &[&seeds],
-        )?;
-
-        // saturating_sub: blaze_minted_gsol is bookkeeping only and does not
+        let properties = (&*self).into();
+        SplStakePoolAdapter::load(&self.stake_pool)?.withdraw_sol(&properties, lamports)?;
+
+        // saturating_sub: the minted gSOL counter is bookkeeping only and does not
         // control SOL disbursement (that is governed by actual bSOL balance and
         // pool exchange rate). Yield appreciation can cause the tracked value
         // to drift below the real amount, so we clamp to zero rather than
         // panic, which would block users from unstaking.
-        let state = &mut self.state;
-        self.state.blaze_minted_gsol = state.blaze_minted_gsol.saturating_sub(lamports);
+        let minted_gsol = minted_gsol_counter(&mut self.state, &mut self.pool_entry);
+        *minted_gsol = minted_gsol.saturating_sub(lamports);
 
         Ok(())
     }
 }
+
+pub fn spl_withdraw_sol_handler(ctx: Context<SplWithdrawSol>, lamports: u64) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::SPL_WITHDRAW)?;
+    ctx.accounts.withdraw_sol(lamports)?;
+
+    TokenUtils::burn(
+        lamports,
+        &ctx.accounts.gsol_mint.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.user_gsol_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+
+    emit_cpi!(LiquidUnstakeEvent {
+        state: ctx.accounts.state.key(),
+        staker: ctx.accounts.user.key(),
+        gsol_burned: lamports,
+        marinade_lamports: 0,
+        blaze_lamports: lamports,
+    });
+    Ok(())
+}
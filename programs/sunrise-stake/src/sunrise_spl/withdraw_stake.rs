@@ -1,6 +1,10 @@
 use crate::{
+    adapters::{SplStakePoolAdapter, StakePoolAdapter},
+    events::OrderUnstakeEvent,
+    state::{operation, PoolEntry},
+    sunrise_spl::{is_spl_pool, minted_gsol_counter, PoolAuthoritySeeds},
     utils::{seeds, spl, token as TokenUtils},
-    State,
+    ErrorCode, State,
 };
 use anchor_lang::{
     prelude::*,
@@ -47,12 +51,13 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 ///  12. `[]` Stake program id,
 ///      userdata: amount of pool tokens to withdraw
 
-#[derive(Accounts)]
+#[event_cpi]
+#[derive(Accounts, Clone)]
 pub struct SplWithdrawStake<'info> {
     #[account(
         mut,
         has_one = gsol_mint,
-        constraint = state.blaze_state == *stake_pool.key
+        constraint = is_spl_pool(&state, &pool_entry, stake_pool.key) @ ErrorCode::PoolNotRegistered
     )]
     pub state: Box<Account<'info, State>>,
     #[account(mut)]
@@ -72,8 +77,9 @@ pub struct SplWithdrawStake<'info> {
     #[account(mut, token::authority = bsol_account_authority)]
     pub bsol_token_account: Account<'info, TokenAccount>,
     #[account(
-        seeds = [state.key().as_ref(), seeds::BSOL_ACCOUNT],
-        bump = state.bsol_authority_bump
+        constraint = *bsol_account_authority.key
+            == PoolAuthoritySeeds::new(&state, &pool_entry).address()?
+            @ ErrorCode::InvalidPoolAuthority
     )]
     /// CHECK:
     pub bsol_account_authority: AccountInfo<'info>,
@@ -81,19 +87,26 @@ pub struct SplWithdrawStake<'info> {
     #[account(mut)]
     /// CHECK:
     pub stake_pool: AccountInfo<'info>,
-    /// CHECK:
+    #[account(
+        mut,
+        seeds = [state.key().as_ref(), seeds::POOL_ENTRY, stake_pool.key.as_ref()],
+        bump = pool_entry.bump,
+    )]
+    /// The pool's entry, if the pool is not Blaze
+    pub pool_entry: Option<Box<Account<'info, PoolEntry>>>,
+    /// CHECK: Checked against the stake pool
     pub stake_pool_withdraw_authority: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub validator_stake_list: AccountInfo<'info>,
     #[account(mut)]
     /// CHECK:
     pub stake_account_to_split: AccountInfo<'info>, //either validator/reserve
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub manager_fee_account: AccountInfo<'info>,
     #[account(mut)]
-    /// CHECK:
+    /// CHECK: Checked against the stake pool
     pub stake_pool_token_mint: AccountInfo<'info>,
     /// CHECK:
     pub sysvar_clock: AccountInfo<'info>,
@@ -114,67 +127,11 @@ impl SplWithdrawStake<'_> {
         Ok(())
     }
 
-    fn calculate_bsol_from_lamports(&self, lamports: u64) -> Result<u64> {
-        let stake_pool = spl::deserialize_spl_stake_pool(&self.stake_pool)?;
-        let bsol = spl::calc_bsol_from_lamports(&stake_pool, lamports)?;
-        Ok(bsol)
-    }
-
     pub fn withdraw_stake(&mut self, lamports: u64) -> Result<()> {
         self.check_stake_pool_program()?;
 
-        let bump = self.state.bsol_authority_bump;
-        let state_key = self.state.to_account_info().key;
-        let seeds = [state_key.as_ref(), seeds::BSOL_ACCOUNT, &[bump]];
-
-        let pool_tokens = self.calculate_bsol_from_lamports(lamports)?;
-
-        // Build instruction data with discriminator 12 for withdrawStake
-        let mut data = vec![12u8];
-        data.extend_from_slice(&pool_tokens.to_le_bytes());
-
-        // Build accounts list
-        let accounts = vec![
-            AccountMeta::new(*self.stake_pool.key, false),
-            AccountMeta::new(*self.validator_stake_list.key, false),
-            AccountMeta::new_readonly(*self.stake_pool_withdraw_authority.key, false),
-            AccountMeta::new(*self.stake_account_to_split.key, false),
-            AccountMeta::new(*self.user_new_stake_account.key, false),
-            AccountMeta::new_readonly(*self.user.key, false),
-            AccountMeta::new_readonly(*self.bsol_account_authority.key, true),
-            AccountMeta::new(self.bsol_token_account.key(), false),
-            AccountMeta::new(*self.manager_fee_account.key, false),
-            AccountMeta::new(*self.stake_pool_token_mint.key, false),
-            AccountMeta::new_readonly(*self.sysvar_clock.key, false),
-            AccountMeta::new_readonly(self.token_program.key(), false),
-            AccountMeta::new_readonly(*self.native_stake_program.key, false),
-        ];
-
-        let instruction = Instruction {
-            program_id: SPL_STAKE_POOL_ID,
-            accounts,
-            data,
-        };
-
-        invoke_signed(
-            &instruction,
-            &[
-                self.stake_pool.clone(),
-                self.validator_stake_list.clone(),
-                self.stake_pool_withdraw_authority.clone(),
-                self.stake_account_to_split.clone(),
-                self.user_new_stake_account.clone(),
-                self.user.to_account_info(),
-                self.bsol_account_authority.clone(),
-                self.bsol_token_account.to_account_info(),
-                self.manager_fee_account.clone(),
-                self.stake_pool_token_mint.clone(),
-                self.sysvar_clock.clone(),
-                self.token_program.to_account_info(),
-                self.native_stake_program.clone(),
-            ],
-            &[&seeds],
-        )?;
+        let properties = (&*self).into();
+        SplStakePoolAdapter::load(&self.stake_pool)?.withdraw_stake(&properties, lamports)?;
 
         // Fees may apply so we might be burning more than the user expects
 
@@ -190,9 +147,142 @@ impl SplWithdrawStake<'_> {
             &self.token_program,
         )?;
 
-        let state = &mut self.state;
-        self.state.blaze_minted_gsol = state.blaze_minted_gsol.checked_sub(lamports).unwrap();
+        // Once yield has accrued the counter can be below the amount being unstaked,
+        // so clamp it as SplWithdrawSol::withdraw_sol does
+        let minted_gsol = minted_gsol_counter(&mut self.state, &mut self.pool_entry);
+        *minted_gsol = minted_gsol.saturating_sub(lamports);
 
         Ok(())
     }
 }
+
+/// The accounts needed to withdraw a stake account from an SPL pool on behalf of this sunrise instance,
+/// burning pool tokens held by the pool authority PDA.
+pub struct SplWithdrawStakeProperties<'info> {
+    pub authority_seeds: PoolAuthoritySeeds,
+    pub bsol_token_account: AccountInfo<'info>,
+    pub bsol_account_authority: AccountInfo<'info>,
+    pub stake_pool: AccountInfo<'info>,
+    pub stake_pool_withdraw_authority: AccountInfo<'info>,
+    pub validator_stake_list: AccountInfo<'info>,
+    pub stake_account_to_split: AccountInfo<'info>,
+    /// An uninitialized stake account to receive the withdrawn stake
+    pub new_stake_account: AccountInfo<'info>,
+    /// Set as the stake and withdraw authority of the new stake account
+    pub recipient: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub stake_pool_token_mint: AccountInfo<'info>,
+    pub sysvar_clock: AccountInfo<'info>,
+    pub native_stake_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+impl<'a> From<SplWithdrawStake<'a>> for SplWithdrawStakeProperties<'a> {
+    fn from(withdraw: SplWithdrawStake<'a>) -> Self {
+        Self {
+            authority_seeds: PoolAuthoritySeeds::new(&withdraw.state, &withdraw.pool_entry),
+            bsol_token_account: withdraw.bsol_token_account.to_account_info(),
+            bsol_account_authority: withdraw.bsol_account_authority,
+            stake_pool: withdraw.stake_pool,
+            stake_pool_withdraw_authority: withdraw.stake_pool_withdraw_authority,
+            validator_stake_list: withdraw.validator_stake_list,
+            stake_account_to_split: withdraw.stake_account_to_split,
+            new_stake_account: withdraw.user_new_stake_account,
+            recipient: withdraw.user.to_account_info(),
+            manager_fee_account: withdraw.manager_fee_account,
+            stake_pool_token_mint: withdraw.stake_pool_token_mint,
+            sysvar_clock: withdraw.sysvar_clock,
+            native_stake_program: withdraw.native_stake_program,
+            token_program: withdraw.token_program.to_account_info(),
+        }
+    }
+}
+impl<'a> From<&SplWithdrawStake<'a>> for SplWithdrawStakeProperties<'a> {
+    fn from(withdraw: &SplWithdrawStake<'a>) -> Self {
+        withdraw.to_owned().into()
+    }
+}
+
+/// Withdraw `lamports` worth of bSOL from the pool as a stake account owned by the recipient.
+/// Does not touch any gSOL accounting.
+pub fn withdraw_stake_from_pool(
+    properties: &SplWithdrawStakeProperties,
+    lamports: u64,
+) -> Result<()> {
+    let seeds = properties.authority_seeds.seeds();
+
+    let stake_pool = spl::deserialize_spl_stake_pool(&properties.stake_pool)?;
+    stake_pool.check_accounts(
+        properties.stake_pool.key,
+        properties.stake_pool_token_mint.key,
+        properties.manager_fee_account.key,
+        properties.stake_pool_withdraw_authority.key,
+    )?;
+    stake_pool.check_validator_list(properties.validator_stake_list.key)?;
+    let pool_tokens = spl::calc_bsol_from_lamports(&stake_pool, lamports)?;
+
+    // Build instruction data with discriminator 10 for WithdrawStake
+    // See: https://github.com/solana-program/stake-pool/blob/main/program/src/instruction.rs
+    let mut data = vec![10u8];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    // Build accounts list
+    let accounts = vec![
+        AccountMeta::new(*properties.stake_pool.key, false),
+        AccountMeta::new(*properties.validator_stake_list.key, false),
+        AccountMeta::new_readonly(*properties.stake_pool_withdraw_authority.key, false),
+        AccountMeta::new(*properties.stake_account_to_split.key, false),
+        AccountMeta::new(*properties.new_stake_account.key, false),
+        AccountMeta::new_readonly(*properties.recipient.key, false),
+        AccountMeta::new_readonly(*properties.bsol_account_authority.key, true),
+        AccountMeta::new(*properties.bsol_token_account.key, false),
+        AccountMeta::new(*properties.manager_fee_account.key, false),
+        AccountMeta::new(*properties.stake_pool_token_mint.key, false),
+        AccountMeta::new_readonly(*properties.sysvar_clock.key, false),
+        AccountMeta::new_readonly(*properties.token_program.key, false),
+        AccountMeta::new_readonly(*properties.native_stake_program.key, false),
+    ];
+
+    let instruction = Instruction {
+        program_id: SPL_STAKE_POOL_ID,
+        accounts,
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            properties.stake_pool.clone(),
+            properties.validator_stake_list.clone(),
+            properties.stake_pool_withdraw_authority.clone(),
+            properties.stake_account_to_split.clone(),
+            properties.new_stake_account.clone(),
+            properties.recipient.clone(),
+            properties.bsol_account_authority.clone(),
+            properties.bsol_token_account.clone(),
+            properties.manager_fee_account.clone(),
+            properties.stake_pool_token_mint.clone(),
+            properties.sysvar_clock.clone(),
+            properties.token_program.clone(),
+            properties.native_stake_program.clone(),
+        ],
+        &[&seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn spl_withdraw_stake_handler(ctx: Context<SplWithdrawStake>, lamports: u64) -> Result<()> {
+    ctx.accounts
+        .state
+        .check_not_paused(operation::SPL_WITHDRAW)?;
+    ctx.accounts.withdraw_stake(lamports)?;
+
+    emit_cpi!(OrderUnstakeEvent {
+        state: ctx.accounts.state.key(),
+        staker: ctx.accounts.user.key(),
+        ticket: ctx.accounts.user_new_stake_account.key(),
+        gsol_burned: lamports,
+        lamports: ctx.accounts.user_new_stake_account.lamports(),
+    });
+    Ok(())
+}
@@ -1,6 +1,9 @@
 use crate::ErrorCode;
 use anchor_lang::prelude::*;
 
+/// 100% expressed in basis points
+pub const MAX_BASIS_POINTS: u64 = 10_000;
+
 /// calculate amount*numerator/denominator
 /// as value  = shares * share_price where share_price=total_value/total_shares
 /// or shares = amount_value / share_price where share_price=total_value/total_shares
@@ -12,3 +15,40 @@ pub fn proportional(amount: u64, numerator: u64, denominator: u64) -> Result<u64
     u64::try_from((amount as u128) * (numerator as u128) / (denominator as u128))
         .map_err(|_| error!(ErrorCode::CalculationFailure))
 }
+
+/// split amount into two parts in proportion to their weights
+/// if both weights are zero, the whole amount is allocated to the first part
+pub fn split_proportionally(amount: u64, weight_a: u64, weight_b: u64) -> Result<(u64, u64)> {
+    let total_weight = weight_a
+        .checked_add(weight_b)
+        .ok_or(error!(ErrorCode::CalculationFailure))?;
+    if total_weight == 0 {
+        return Ok((amount, 0));
+    }
+    let part_b = proportional(amount, weight_b, total_weight)?;
+    Ok((amount - part_b, part_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_proportionally() {
+        assert_eq!(split_proportionally(100, 3, 1).unwrap(), (75, 25));
+        assert_eq!(split_proportionally(100, 0, 5).unwrap(), (0, 100));
+        assert_eq!(split_proportionally(100, 5, 0).unwrap(), (100, 0));
+    }
+
+    #[test]
+    fn test_split_proportionally_no_weights() {
+        assert_eq!(split_proportionally(100, 0, 0).unwrap(), (100, 0));
+    }
+
+    #[test]
+    fn test_split_proportionally_rounding_preserves_amount() {
+        let (a, b) = split_proportionally(10, 1, 2).unwrap();
+        assert_eq!(a + b, 10);
+        assert_eq!(b, 6);
+    }
+}
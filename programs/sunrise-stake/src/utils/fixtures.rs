@@ -0,0 +1,11 @@
+//! Loading accounts dumped to packages/tests/fixtures for use in unit tests
+
+/// The data of an account dumped with `solana account --output json`
+pub fn load_account_data(json: &str) -> Vec<u8> {
+    use base64::Engine;
+    let dump: serde_json::Value = serde_json::from_str(json).unwrap();
+    let data = dump["account"]["data"][0].as_str().unwrap();
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .unwrap()
+}
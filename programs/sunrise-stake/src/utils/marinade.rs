@@ -1,6 +1,6 @@
 use crate::instructions::{
-    DepositSplStakeToLiquid, InitEpochReport, MoveSplLiquidToMarinade, RecoverTickets,
-    UpdateEpochReport,
+    ClaimUnstakeTicketOnBehalf, ClaimUnstakeTickets, DepositSplStakeToLiquid, InitEpochReport,
+    MoveSplLiquidToMarinade, RecoverTickets, UpdateEpochReport,
 };
 use crate::marinade::{
     accounts::MarinadeState,
@@ -20,9 +20,11 @@ use crate::marinade::{
     ID as MARINADE_PROGRAM_ID,
 };
 use crate::{
-    utils::{calc::proportional, seeds::MSOL_ACCOUNT, spl},
+    adapters::{MarinadeAdapter, SplStakePoolAdapter, StakePoolAdapter},
+    utils::{calc::proportional, seeds::MSOL_ACCOUNT},
     ClaimUnstakeTicket, Deposit, DepositStakeAccount, EpochReportAccount, ErrorCode,
-    ExtractToTreasury, LiquidUnstake, OrderUnstake, State, TriggerPoolRebalance,
+    ExtractToTreasury, ExtractToTreasuryV2, GetReserves, LiquidUnstake, OrderUnstake, State,
+    TriggerPoolRebalance,
 };
 use anchor_lang::{
     context::CpiContext,
@@ -31,6 +33,9 @@ use anchor_lang::{
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+/// Seed of the marinade liquidity pool sol leg PDA
+const LIQ_POOL_SOL_LEG_SEED: &[u8] = b"liq_sol";
+
 /// Deserialize MarinadeState from an UncheckedAccount with custom discriminator checking
 ///
 /// # Background
@@ -94,7 +99,7 @@ pub struct GenericUnstakeProperties<'info> {
     get_msol_from: Box<Account<'info, TokenAccount>>,
     get_msol_from_authority: SystemAccount<'info>,
     /// CHECK: Set by the calling function
-    recipient: AccountInfo<'info>,
+    pub recipient: AccountInfo<'info>,
     system_program: Program<'info, System>,
     token_program: Program<'info, Token>,
     marinade_program: Program<'info, MarinadeFinance>,
@@ -145,6 +150,29 @@ impl<'a> From<&ExtractToTreasury<'a>> for GenericUnstakeProperties<'a> {
         properties.to_owned().into()
     }
 }
+impl<'a> From<ExtractToTreasuryV2<'a>> for GenericUnstakeProperties<'a> {
+    fn from(properties: ExtractToTreasuryV2<'a>) -> Self {
+        Self {
+            state: properties.state,
+            marinade_state: properties.marinade_state,
+            msol_mint: properties.msol_mint,
+            liq_pool_sol_leg_pda: properties.liq_pool_sol_leg_pda,
+            liq_pool_msol_leg: properties.liq_pool_msol_leg,
+            treasury_msol_account: properties.treasury_msol_account,
+            get_msol_from: properties.get_msol_from,
+            get_msol_from_authority: properties.get_msol_from_authority,
+            recipient: properties.treasury.to_account_info(),
+            system_program: properties.system_program,
+            token_program: properties.token_program,
+            marinade_program: properties.marinade_program,
+        }
+    }
+}
+impl<'a> From<&ExtractToTreasuryV2<'a>> for GenericUnstakeProperties<'a> {
+    fn from(properties: &ExtractToTreasuryV2<'a>) -> Self {
+        properties.to_owned().into()
+    }
+}
 
 pub struct OrderUnstakeProperties<'info> {
     state: Box<Account<'info, State>>,
@@ -180,6 +208,32 @@ impl<'a> From<&OrderUnstake<'a>> for OrderUnstakeProperties<'a> {
         unstake.to_owned().into()
     }
 }
+impl<'a> TryFrom<ExtractToTreasuryV2<'a>> for OrderUnstakeProperties<'a> {
+    type Error = Error;
+    fn try_from(extract: ExtractToTreasuryV2<'a>) -> Result<Self> {
+        let new_ticket_account = extract
+            .new_ticket_account
+            .ok_or(ErrorCode::MissingAccount)?;
+        Ok(Self {
+            state: extract.state,
+            marinade_state: extract.marinade_state,
+            msol_mint: *extract.msol_mint,
+            burn_msol_from: *extract.get_msol_from,
+            burn_msol_authority: extract.get_msol_from_authority,
+            new_ticket_account: new_ticket_account.to_account_info(),
+            token_program: extract.token_program,
+            marinade_program: extract.marinade_program,
+            rent: extract.rent,
+            clock: extract.clock,
+        })
+    }
+}
+impl<'a> TryFrom<&ExtractToTreasuryV2<'a>> for OrderUnstakeProperties<'a> {
+    type Error = Error;
+    fn try_from(extract: &ExtractToTreasuryV2<'a>) -> Result<Self> {
+        extract.to_owned().try_into()
+    }
+}
 impl<'a> From<TriggerPoolRebalance<'a>> for OrderUnstakeProperties<'a> {
     fn from(trigger_pool_rebalance: TriggerPoolRebalance<'a>) -> Self {
         Self {
@@ -234,6 +288,43 @@ impl<'a> From<&ClaimUnstakeTicket<'a>> for ClaimUnstakeTicketProperties<'a> {
         claim.to_owned().into()
     }
 }
+impl<'a> From<ClaimUnstakeTicketOnBehalf<'a>> for ClaimUnstakeTicketProperties<'a> {
+    fn from(claim: ClaimUnstakeTicketOnBehalf<'a>) -> Self {
+        Self {
+            marinade_state: claim.marinade_state,
+            reserve_pda: claim.reserve_pda.to_account_info(),
+            ticket_account: claim.marinade_ticket_account.to_account_info(),
+            transfer_sol_to: claim.msol_authority.to_account_info(),
+            marinade_program: claim.marinade_program,
+            clock: claim.clock,
+            system_program: claim.system_program,
+        }
+    }
+}
+impl<'a> From<&ClaimUnstakeTicketOnBehalf<'a>> for ClaimUnstakeTicketProperties<'a> {
+    fn from(claim: &ClaimUnstakeTicketOnBehalf<'a>) -> Self {
+        claim.to_owned().into()
+    }
+}
+impl<'a> From<ClaimUnstakeTickets<'a>> for ClaimUnstakeTicketProperties<'a> {
+    fn from(claim: ClaimUnstakeTickets<'a>) -> Self {
+        Self {
+            marinade_state: claim.marinade_state,
+            reserve_pda: claim.reserve_pda.to_account_info(),
+            transfer_sol_to: claim.msol_authority.to_account_info(),
+            // Temporary and will be overwritten with each ticket
+            ticket_account: claim.marinade_program.to_account_info(),
+            marinade_program: claim.marinade_program,
+            clock: claim.clock,
+            system_program: claim.system_program,
+        }
+    }
+}
+impl<'a> From<&ClaimUnstakeTickets<'a>> for ClaimUnstakeTicketProperties<'a> {
+    fn from(claim: &ClaimUnstakeTickets<'a>) -> Self {
+        claim.to_owned().into()
+    }
+}
 impl<'a> From<RecoverTickets<'a>> for ClaimUnstakeTicketProperties<'a> {
     fn from(recover_tickets: RecoverTickets<'a>) -> Self {
         Self {
@@ -599,6 +690,26 @@ impl<'a> From<&ExtractToTreasury<'a>> for CalculateExtractableYieldProperties<'a
         extract_to_treasury.to_owned().into()
     }
 }
+impl<'a> From<ExtractToTreasuryV2<'a>> for CalculateExtractableYieldProperties<'a> {
+    fn from(extract_to_treasury: ExtractToTreasuryV2<'a>) -> Self {
+        Self {
+            marinade_state: extract_to_treasury.marinade_state,
+            blaze_state: extract_to_treasury.blaze_state,
+            gsol_mint: extract_to_treasury.gsol_mint,
+            liq_pool_mint: extract_to_treasury.liq_pool_mint,
+            liq_pool_sol_leg_pda: extract_to_treasury.liq_pool_sol_leg_pda,
+            liq_pool_msol_leg: extract_to_treasury.liq_pool_msol_leg,
+            liq_pool_token_account: extract_to_treasury.liq_pool_token_account,
+            get_msol_from: extract_to_treasury.get_msol_from,
+            get_bsol_from: extract_to_treasury.get_bsol_from,
+        }
+    }
+}
+impl<'a> From<&ExtractToTreasuryV2<'a>> for CalculateExtractableYieldProperties<'a> {
+    fn from(extract_to_treasury: &ExtractToTreasuryV2<'a>) -> Self {
+        extract_to_treasury.to_owned().into()
+    }
+}
 impl<'a> From<InitEpochReport<'a>> for CalculateExtractableYieldProperties<'a> {
     fn from(init_epoch_pool_report: InitEpochReport<'a>) -> Self {
         Self {
@@ -659,44 +770,101 @@ impl<'a> From<&UpdateEpochReport<'a>> for CalculateExtractableYieldProperties<'a
         update_epoch_report.to_owned().into()
     }
 }
-/// Calculate the current recoverable yield (in msol) from marinade.
-/// Recoverable yield is defined as the sol value of the msol + lp tokens
-/// that are not matched by gsol
-pub fn calculate_extractable_yield(accounts: &CalculateExtractableYieldProperties) -> Result<u64> {
-    let blaze_stake_pool = spl::deserialize_spl_stake_pool(&accounts.blaze_state)?;
-    let marinade_state = deserialize_marinade_state(&accounts.marinade_state)?;
+impl<'a> From<GetReserves<'a>> for CalculateExtractableYieldProperties<'a> {
+    fn from(get_reserves: GetReserves<'a>) -> Self {
+        Self {
+            marinade_state: get_reserves.marinade_state,
+            blaze_state: get_reserves.blaze_state,
+            gsol_mint: get_reserves.gsol_mint,
+            liq_pool_mint: get_reserves.liq_pool_mint,
+            liq_pool_sol_leg_pda: get_reserves.liq_pool_sol_leg_pda,
+            liq_pool_msol_leg: get_reserves.liq_pool_msol_leg,
+            liq_pool_token_account: get_reserves.liq_pool_token_account,
+            get_msol_from: get_reserves.get_msol_from,
+            get_bsol_from: get_reserves.get_bsol_from,
+        }
+    }
+}
+impl<'a> From<&GetReserves<'a>> for CalculateExtractableYieldProperties<'a> {
+    fn from(get_reserves: &GetReserves<'a>) -> Self {
+        get_reserves.to_owned().into()
+    }
+}
+/// The sol value of the holdings of this sunrise instance in each stake pool
+pub struct HoldingsValue {
+    /// The sol value of this instance's share of the marinade liquidity pool
+    pub lp: u64,
+    /// The sol value of all msol
+    pub msol: u64,
+    /// The sol value of all bsol
+    pub blaze: u64,
+    /// The sol value of the pool tokens of every pool with an entry, see value_pool_entries
+    pub entry_pools: u64,
+}
+impl HoldingsValue {
+    /// The sol value of all msol + lp tokens
+    pub fn marinade(&self) -> u64 {
+        self.lp.checked_add(self.msol).unwrap()
+    }
+}
+
+/// `entry_pools` is the value of the holdings in pools with an entry, from value_pool_entries
+pub fn calculate_holdings_value(
+    accounts: &CalculateExtractableYieldProperties,
+    entry_pools: u64,
+) -> Result<HoldingsValue> {
+    let blaze = SplStakePoolAdapter::load(&accounts.blaze_state)?;
+    require_keys_eq!(
+        accounts.get_bsol_from.mint,
+        blaze.stake_pool.pool_mint,
+        crate::ErrorCode::InvalidStakePoolAccount
+    );
+    let marinade = MarinadeAdapter::load(&accounts.marinade_state)?;
+    let marinade_state = &marinade.marinade_state;
 
     let liquidity_pool_balance = current_liq_pool_balance(
-        &marinade_state,
+        marinade_state,
         &accounts.liq_pool_mint,
         &accounts.liq_pool_token_account,
         &accounts.liq_pool_sol_leg_pda,
         &accounts.liq_pool_msol_leg,
     )?;
     // Calculate the sol value of all msol + lp tokens held by this sunrise instance
-    let lp_value = liquidity_pool_balance.sol_value(&marinade_state);
-    let msol_value =
-        calc_lamports_from_msol_amount(&marinade_state, accounts.get_msol_from.amount)?;
-    let bsol_value =
-        spl::calc_lamports_from_bsol_amount(&blaze_stake_pool, accounts.get_bsol_from.amount)?;
-    let total_staked_value = lp_value
-        .checked_add(msol_value)
-        .unwrap()
-        .checked_add(bsol_value)
-        .expect("total_staked_value");
+    let lp_value = liquidity_pool_balance.sol_value(marinade_state);
+    let msol_value = marinade.value_of_holdings(accounts.get_msol_from.amount)?;
+    let bsol_value = blaze.value_of_holdings(accounts.get_bsol_from.amount)?;
+
+    Ok(HoldingsValue {
+        lp: lp_value,
+        msol: msol_value,
+        blaze: bsol_value,
+        entry_pools,
+    })
+}
 
-    let gsol_supply = accounts.gsol_mint.supply;
-    let total_extractable_yield = total_staked_value.saturating_sub(gsol_supply);
+/// Calculate the current recoverable yield (in msol) from marinade.
+/// Recoverable yield is defined as the sol value of the msol + lp tokens
+/// that are not matched by gsol
+pub fn calculate_extractable_yield(
+    accounts: &CalculateExtractableYieldProperties,
+    entry_pools: u64,
+) -> Result<u64> {
+    let holdings_value = calculate_holdings_value(accounts, entry_pools)?;
+    Ok(extractable_yield_of_holdings(
+        &holdings_value,
+        accounts.gsol_mint.supply,
+    ))
+}
 
-    // TODO Remove when no longer debugging
-    msg!("lp_value: {}", lp_value);
-    msg!("msol_value: {}", msol_value);
-    msg!("bsol_value: {}", bsol_value);
-    msg!("total_staked_value: {}", total_staked_value);
-    msg!("gsol_supply: {}", gsol_supply);
-    msg!("total_extractable_yield: {}", total_extractable_yield);
+/// The sol value of the holdings that is not matched by gsol
+pub fn extractable_yield_of_holdings(holdings_value: &HoldingsValue, gsol_supply: u64) -> u64 {
+    let total_staked_value = holdings_value
+        .marinade()
+        .checked_add(holdings_value.blaze)
+        .and_then(|value| value.checked_add(holdings_value.entry_pools))
+        .expect("total_staked_value");
 
-    Ok(total_extractable_yield)
+    total_staked_value.saturating_sub(gsol_supply)
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -725,7 +893,7 @@ impl LiquidityPoolBalance {
     }
 
     // The value of both legs of the liquidity pool balance in SOL
-    fn sol_value(&self, marinade_state: &MarinadeState) -> u64 {
+    pub fn sol_value(&self, marinade_state: &MarinadeState) -> u64 {
         let lamports = self.lamports;
         let msol = calc_lamports_from_msol_amount(marinade_state, self.msol).unwrap();
         lamports.checked_add(msol).expect("sol_value")
@@ -801,6 +969,42 @@ pub fn current_liq_pool_balance<'a>(
     Ok(sunrise_liq_pool_balance)
 }
 
+/// Check the liquidity pool accounts are the ones recorded by the marinade state at `marinade_state_address`.
+/// Only needed where they are not passed on to marinade, which checks them itself.
+pub fn check_liq_pool_accounts(
+    marinade_state: &MarinadeState,
+    marinade_state_address: &Pubkey,
+    liq_pool_mint: &Pubkey,
+    liq_pool_sol_leg_pda: &Pubkey,
+    liq_pool_msol_leg: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        *liq_pool_mint,
+        marinade_state.liq_pool.lp_mint,
+        ErrorCode::InvalidMarinadeAccount
+    );
+    require_keys_eq!(
+        *liq_pool_msol_leg,
+        marinade_state.liq_pool.msol_leg,
+        ErrorCode::InvalidMarinadeAccount
+    );
+    let sol_leg = Pubkey::create_program_address(
+        &[
+            marinade_state_address.as_ref(),
+            LIQ_POOL_SOL_LEG_SEED,
+            &[marinade_state.liq_pool.sol_leg_bump_seed],
+        ],
+        &MARINADE_PROGRAM_ID,
+    )
+    .map_err(|_| ErrorCode::InvalidMarinadeAccount)?;
+    require_keys_eq!(
+        *liq_pool_sol_leg_pda,
+        sol_leg,
+        ErrorCode::InvalidMarinadeAccount
+    );
+    Ok(())
+}
+
 fn total_liq_pool(
     marinade_state: &MarinadeState,
     liq_pool_mint: &Account<Mint>,
@@ -1058,3 +1262,81 @@ pub fn get_delegated_stake_amount(stake_account: &AccountInfo) -> Result<u64> {
         None => Err(crate::ErrorCode::NotDelegated.into()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::fixtures::load_account_data;
+
+    const MARINADE_STATE: Pubkey = pubkey!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC");
+    const LIQ_POOL_MINT: Pubkey = pubkey!("LPmSozJJ8Jh69ut2WP3XmVohTjL4ipR18yiCzxrUmVj");
+    const LIQ_POOL_SOL_LEG: Pubkey = pubkey!("UefNb6z6yvArqe4cJHTXCqStRsKmWhGxnZzuHbikP5Q");
+    const LIQ_POOL_MSOL_LEG: Pubkey = pubkey!("7GgPYjS5Dza89wV6FpZ23kUJRG5vbQ1GM25ezspYFSoE");
+
+    fn fixture_marinade_state() -> MarinadeState {
+        let data = load_account_data(include_str!(
+            "../../../../packages/tests/fixtures/scenarios/marinade_state.json"
+        ));
+        MarinadeState::deserialize(&mut &data[8..]).unwrap()
+    }
+
+    #[test]
+    fn test_check_liq_pool_accounts() {
+        let marinade_state = fixture_marinade_state();
+        let check = |state_address, mint, sol_leg, msol_leg| {
+            check_liq_pool_accounts(&marinade_state, state_address, mint, sol_leg, msol_leg)
+        };
+
+        assert!(check(
+            &MARINADE_STATE,
+            &LIQ_POOL_MINT,
+            &LIQ_POOL_SOL_LEG,
+            &LIQ_POOL_MSOL_LEG
+        )
+        .is_ok());
+
+        let other = Pubkey::new_unique();
+        assert!(check(
+            &MARINADE_STATE,
+            &other,
+            &LIQ_POOL_SOL_LEG,
+            &LIQ_POOL_MSOL_LEG
+        )
+        .is_err());
+        assert!(check(&MARINADE_STATE, &LIQ_POOL_MINT, &other, &LIQ_POOL_MSOL_LEG).is_err());
+        assert!(check(&MARINADE_STATE, &LIQ_POOL_MINT, &LIQ_POOL_SOL_LEG, &other).is_err());
+        // the sol leg is a PDA of the marinade state it belongs to
+        assert!(check(
+            &other,
+            &LIQ_POOL_MINT,
+            &LIQ_POOL_SOL_LEG,
+            &LIQ_POOL_MSOL_LEG
+        )
+        .is_err());
+        // the legs cannot be swapped
+        assert!(check(
+            &MARINADE_STATE,
+            &LIQ_POOL_MINT,
+            &LIQ_POOL_MSOL_LEG,
+            &LIQ_POOL_SOL_LEG
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_extractable_yield_of_holdings_counts_entry_pools() {
+        let holdings_value = HoldingsValue {
+            lp: 100,
+            msol: 400,
+            blaze: 300,
+            entry_pools: 250,
+        };
+        assert_eq!(extractable_yield_of_holdings(&holdings_value, 1_000), 50);
+        // without the entry pools, their gSOL would appear unbacked
+        let holdings_value = HoldingsValue {
+            entry_pools: 0,
+            ..holdings_value
+        };
+        assert_eq!(extractable_yield_of_holdings(&holdings_value, 1_000), 0);
+    }
+}
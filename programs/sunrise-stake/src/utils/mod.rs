@@ -1,4 +1,6 @@
 pub(crate) mod calc;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub(crate) mod marinade;
 pub(crate) mod metaplex;
 pub(crate) mod seeds;
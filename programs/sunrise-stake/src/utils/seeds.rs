@@ -10,3 +10,11 @@ pub const LOCK_TOKEN_ACCOUNT: &[u8] = b"lock_token_account";
 pub const IMPACT_NFT_MINT_AUTHORITY: &[u8] = b"impact_nft_mint_authority";
 pub const IMPACT_NFT_MINT_ACCOUNT: &[u8] = b"impact_nft_mint_account";
 pub const SPL_REBALANCE_STAKE_ACCOUNT: &[u8] = b"spl_rebalance_stake";
+pub const TICKET_RECEIPT_MINT: &[u8] = b"ticket_receipt_mint";
+pub const TICKET_RECEIPT_AUTHORITY: &[u8] = b"ticket_receipt_authority";
+pub const YIELD_RECIPIENTS: &[u8] = b"yield_recipients";
+pub const EPOCH_REPORT_HISTORY: &[u8] = b"report_history";
+pub const PENDING_STATE_CHANGE: &[u8] = b"pending_state_change";
+pub const POOL_REGISTRY: &[u8] = b"pool_registry";
+pub const POOL_ENTRY: &[u8] = b"pool_entry";
+pub const POOL_AUTHORITY: &[u8] = b"pool_authority";
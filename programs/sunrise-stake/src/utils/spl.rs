@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::borsh0_10::try_from_slice_unchecked;
 
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// The account type of an initialized stake pool, matching the spl-stake-pool AccountType
+pub const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+/// Seed of the pool's withdraw authority PDA
+const WITHDRAW_AUTHORITY_SEED: &[u8] = b"withdraw";
+
 /// Fee structure matching the spl-stake-pool Fee type
 #[derive(Clone, Copy, Debug, Default, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub struct Fee {
@@ -29,23 +38,43 @@ pub struct StakePool {
     pub last_update_epoch: u64,
     pub lockup: Lockup,
     pub epoch_fee: Fee,
-    pub next_epoch_fee: Option<Fee>,
+    pub next_epoch_fee: FutureEpoch<Fee>,
     pub preferred_deposit_validator_vote_address: Option<Pubkey>,
     pub preferred_withdraw_validator_vote_address: Option<Pubkey>,
     pub stake_deposit_fee: Fee,
     pub stake_withdrawal_fee: Fee,
-    pub next_stake_withdrawal_fee: Option<Fee>,
+    pub next_stake_withdrawal_fee: FutureEpoch<Fee>,
     pub stake_referral_fee: u8,
     pub sol_deposit_authority: Option<Pubkey>,
     pub sol_deposit_fee: Fee,
     pub sol_referral_fee: u8,
     pub sol_withdraw_authority: Option<Pubkey>,
     pub sol_withdrawal_fee: Fee,
-    pub next_sol_withdrawal_fee: Option<Fee>,
+    pub next_sol_withdrawal_fee: FutureEpoch<Fee>,
     pub last_epoch_pool_token_supply: u64,
     pub last_epoch_total_lamports: u64,
 }
 
+/// A fee change scheduled by the pool manager, matching the spl-stake-pool FutureEpoch type.
+/// The change takes effect at the pool update after the epoch it was set in,
+/// and so is `Two` in the epoch it was set in and `One` after the first update.
+#[derive(Clone, Copy, Debug, Default, PartialEq, AnchorSerialize, AnchorDeserialize)]
+pub enum FutureEpoch<T> {
+    #[default]
+    None,
+    One(T),
+    Two(T),
+}
+
+impl<T> FutureEpoch<T> {
+    pub fn get(&self) -> Option<&T> {
+        match self {
+            FutureEpoch::None => None,
+            FutureEpoch::One(value) | FutureEpoch::Two(value) => Some(value),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub struct Lockup {
     pub unix_timestamp: i64,
@@ -54,6 +83,18 @@ pub struct Lockup {
 }
 
 impl Fee {
+    /// Whether this fee is a larger proportion than `other`. A zero denominator is a zero fee.
+    pub fn exceeds(&self, other: &Fee) -> bool {
+        if self.denominator == 0 {
+            return false;
+        }
+        if other.denominator == 0 {
+            return self.numerator > 0;
+        }
+        (self.numerator as u128) * (other.denominator as u128)
+            > (other.numerator as u128) * (self.denominator as u128)
+    }
+
     /// Apply the fee to the given amount
     pub fn apply(&self, amount: u64) -> Option<u128> {
         if self.denominator == 0 {
@@ -65,9 +106,94 @@ impl Fee {
     }
 }
 
+impl StakePool {
+    /// Whether update_stake_pool has been run this epoch.
+    /// Until it is, total_lamports does not include the epoch's rewards or losses.
+    pub fn is_current(&self, epoch: u64) -> bool {
+        self.last_update_epoch >= epoch
+    }
+
+    /// The withdraw authority of the pool at `address`, which mints and burns its pool tokens
+    pub fn withdraw_authority(&self, address: &Pubkey) -> Result<Pubkey> {
+        Pubkey::create_program_address(
+            &[
+                address.as_ref(),
+                WITHDRAW_AUTHORITY_SEED,
+                &[self.stake_withdraw_bump_seed],
+            ],
+            &SPL_STAKE_POOL_PROGRAM_ID,
+        )
+        .map_err(|_| crate::ErrorCode::InvalidStakePoolAccount.into())
+    }
+
+    /// Check the accounts passed alongside the pool at `address`, needed by every
+    /// deposit and withdrawal, are the ones the pool records.
+    pub fn check_accounts(
+        &self,
+        address: &Pubkey,
+        pool_mint: &Pubkey,
+        manager_fee_account: &Pubkey,
+        withdraw_authority: &Pubkey,
+    ) -> Result<()> {
+        require_keys_eq!(
+            *pool_mint,
+            self.pool_mint,
+            crate::ErrorCode::InvalidStakePoolAccount
+        );
+        require_keys_eq!(
+            *manager_fee_account,
+            self.manager_fee_account,
+            crate::ErrorCode::InvalidStakePoolAccount
+        );
+        require_keys_eq!(
+            *withdraw_authority,
+            self.withdraw_authority(address)?,
+            crate::ErrorCode::InvalidStakePoolAccount
+        );
+        Ok(())
+    }
+
+    pub fn check_reserve_stake(&self, reserve_stake: &Pubkey) -> Result<()> {
+        require_keys_eq!(
+            *reserve_stake,
+            self.reserve_stake,
+            crate::ErrorCode::InvalidStakePoolAccount
+        );
+        Ok(())
+    }
+
+    pub fn check_validator_list(&self, validator_list: &Pubkey) -> Result<()> {
+        require_keys_eq!(
+            *validator_list,
+            self.validator_list,
+            crate::ErrorCode::InvalidStakePoolAccount
+        );
+        Ok(())
+    }
+
+    /// The higher of the current SOL withdrawal fee and any pending change to it
+    pub fn max_sol_withdrawal_fee(&self) -> Fee {
+        match self.next_sol_withdrawal_fee.get() {
+            Some(next) if next.exceeds(&self.sol_withdrawal_fee) => *next,
+            _ => self.sol_withdrawal_fee,
+        }
+    }
+}
+
+/// Deserialize a stake pool, checking it is owned by the SPL stake pool program
+/// and is a stake pool rather than another of the program's accounts.
 pub fn deserialize_spl_stake_pool(stake_pool_account: &AccountInfo) -> Result<StakePool> {
-    try_from_slice_unchecked::<StakePool>(&stake_pool_account.data.borrow())
-        .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    if stake_pool_account.owner != &SPL_STAKE_POOL_PROGRAM_ID {
+        return Err(crate::ErrorCode::InvalidProgramAccount.into());
+    }
+
+    let stake_pool = try_from_slice_unchecked::<StakePool>(&stake_pool_account.data.borrow())
+        .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+    if stake_pool.account_type != STAKE_POOL_ACCOUNT_TYPE {
+        return Err(crate::ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+
+    Ok(stake_pool)
 }
 
 /// Calculate lamports amount on withdrawal
@@ -86,6 +212,46 @@ pub fn calc_lamports_from_bsol_amount(stake_pool: &StakePool, bsol_balance: u64)
     Ok(calc_lamports_withdraw_amount(stake_pool, bsol_balance).unwrap())
 }
 
+/// The SOL value of `pool_tokens` held by this instance, net of the SOL withdrawal fee.
+///
+/// The fee is the higher of the current fee and any pending change to it.
+/// If the pool has not been updated this epoch, its exchange rate is out of date,
+/// so the lower of the current and previous epoch's rates is used.
+pub fn calc_bsol_holdings_value(
+    stake_pool: &StakePool,
+    pool_tokens: u64,
+    epoch: u64,
+) -> Result<u64> {
+    let fee = stake_pool.max_sol_withdrawal_fee();
+    let fee_tokens = if fee.denominator == 0 {
+        0
+    } else {
+        u64::try_from(
+            fee.apply(pool_tokens)
+                .ok_or(crate::ErrorCode::InvalidCalculation)?,
+        )
+        .map_err(|_| crate::ErrorCode::InvalidCalculation)?
+    };
+    let tokens_after_fee = pool_tokens.saturating_sub(fee_tokens);
+
+    let value = calc_lamports_from_bsol_amount(stake_pool, tokens_after_fee)?;
+    if stake_pool.is_current(epoch) || stake_pool.last_epoch_pool_token_supply == 0 {
+        return Ok(value);
+    }
+
+    msg!(
+        "Stake pool last updated in epoch {}, valuing at the lower of the last two rates",
+        stake_pool.last_update_epoch
+    );
+    let previous_rate_pool = StakePool {
+        total_lamports: stake_pool.last_epoch_total_lamports,
+        pool_token_supply: stake_pool.last_epoch_pool_token_supply,
+        ..stake_pool.clone()
+    };
+    let previous_value = calc_lamports_from_bsol_amount(&previous_rate_pool, tokens_after_fee)?;
+    Ok(value.min(previous_value))
+}
+
 /// Calculate pool tokens for a deposit amount
 #[inline]
 pub fn calc_pool_tokens_for_deposit(stake_pool: &StakePool, stake_lamports: u64) -> Option<u64> {
@@ -152,7 +318,7 @@ mod tests {
                 denominator: 0,
                 numerator: 0,
             },
-            next_epoch_fee: None,
+            next_epoch_fee: FutureEpoch::None,
             preferred_deposit_validator_vote_address: None,
             preferred_withdraw_validator_vote_address: None,
             stake_deposit_fee: Fee {
@@ -163,7 +329,7 @@ mod tests {
                 denominator: 1000,
                 numerator: 3,
             }, // 0.3% fee
-            next_stake_withdrawal_fee: None,
+            next_stake_withdrawal_fee: FutureEpoch::None,
             stake_referral_fee: 0,
             sol_deposit_authority: None,
             sol_deposit_fee: Fee {
@@ -176,7 +342,7 @@ mod tests {
                 denominator: 1000,
                 numerator: 3,
             }, // 0.3% fee
-            next_sol_withdrawal_fee: None,
+            next_sol_withdrawal_fee: FutureEpoch::None,
             last_epoch_pool_token_supply: 0,
             last_epoch_total_lamports: 0,
         }
@@ -319,6 +485,163 @@ mod tests {
         }
     }
 
+    mod holdings_value_tests {
+        use super::*;
+
+        #[test]
+        fn test_holdings_value_deducts_withdrawal_fee() {
+            // 0.3% fee on 1000 tokens = 3 tokens
+            let pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            assert_eq!(calc_bsol_holdings_value(&pool, 1000, 0).unwrap(), 997);
+        }
+
+        #[test]
+        fn test_holdings_value_uses_higher_pending_fee() {
+            let mut pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            pool.next_sol_withdrawal_fee = FutureEpoch::Two(Fee {
+                denominator: 100,
+                numerator: 1,
+            });
+            assert_eq!(calc_bsol_holdings_value(&pool, 1000, 0).unwrap(), 990);
+        }
+
+        #[test]
+        fn test_holdings_value_ignores_lower_pending_fee() {
+            let mut pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            pool.next_sol_withdrawal_fee = FutureEpoch::One(Fee {
+                denominator: 0,
+                numerator: 0,
+            });
+            assert_eq!(calc_bsol_holdings_value(&pool, 1000, 0).unwrap(), 997);
+        }
+
+        #[test]
+        fn test_holdings_value_stale_pool_uses_lower_rate() {
+            // The pool reports 1.1 SOL per token, up from 1.0 in the previous epoch
+            let mut pool = create_test_stake_pool(110_000_000_000, 100_000_000_000);
+            pool.last_update_epoch = 5;
+            pool.last_epoch_total_lamports = 100_000_000_000;
+            pool.last_epoch_pool_token_supply = 100_000_000_000;
+
+            // Up to date: 997 tokens after fees at 1.1
+            assert_eq!(calc_bsol_holdings_value(&pool, 1000, 5).unwrap(), 1096);
+            // Not yet updated in epoch 6: valued at the previous rate of 1.0
+            assert_eq!(calc_bsol_holdings_value(&pool, 1000, 6).unwrap(), 997);
+        }
+
+        #[test]
+        fn test_holdings_value_stale_pool_without_previous_rate() {
+            let mut pool = create_test_stake_pool(110_000_000_000, 100_000_000_000);
+            pool.last_update_epoch = 5;
+            assert_eq!(calc_bsol_holdings_value(&pool, 1000, 6).unwrap(), 1096);
+        }
+
+        #[test]
+        fn test_future_epoch_deserializes_all_variants() {
+            let fee = Fee {
+                denominator: 100,
+                numerator: 1,
+            };
+            for value in [
+                FutureEpoch::None,
+                FutureEpoch::One(fee),
+                FutureEpoch::Two(fee),
+            ] {
+                let bytes = value.try_to_vec().unwrap();
+                assert_eq!(FutureEpoch::<Fee>::try_from_slice(&bytes).unwrap(), value);
+            }
+        }
+    }
+
+    mod validation_tests {
+        use super::*;
+
+        fn deserialize_with_owner(pool: &StakePool, owner: &Pubkey) -> Result<StakePool> {
+            let key = Pubkey::new_unique();
+            let mut lamports = 0;
+            let mut data = pool.try_to_vec().unwrap();
+            let account = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut data,
+                owner,
+                false,
+                0,
+            );
+            deserialize_spl_stake_pool(&account)
+        }
+
+        #[test]
+        fn test_deserialize_valid_pool() {
+            let pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            let deserialized = deserialize_with_owner(&pool, &SPL_STAKE_POOL_PROGRAM_ID).unwrap();
+            assert_eq!(deserialized, pool);
+        }
+
+        #[test]
+        fn test_deserialize_rejects_wrong_owner() {
+            let pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            let result = deserialize_with_owner(&pool, &Pubkey::new_unique());
+            assert_eq!(
+                result.unwrap_err(),
+                crate::ErrorCode::InvalidProgramAccount.into()
+            );
+        }
+
+        #[test]
+        fn test_deserialize_rejects_wrong_account_type() {
+            let mut pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            pool.account_type = 2; // ValidatorList
+            let result = deserialize_with_owner(&pool, &SPL_STAKE_POOL_PROGRAM_ID);
+            assert_eq!(
+                result.unwrap_err(),
+                crate::ErrorCode::AccountDiscriminatorMismatch.into()
+            );
+        }
+
+        #[test]
+        fn test_check_accounts() {
+            let address = Pubkey::new_unique();
+            let (withdraw_authority, bump) = Pubkey::find_program_address(
+                &[address.as_ref(), WITHDRAW_AUTHORITY_SEED],
+                &SPL_STAKE_POOL_PROGRAM_ID,
+            );
+            let mut pool = create_test_stake_pool(1_000_000_000, 1_000_000_000);
+            pool.stake_withdraw_bump_seed = bump;
+            pool.pool_mint = Pubkey::new_unique();
+            pool.manager_fee_account = Pubkey::new_unique();
+
+            assert!(pool
+                .check_accounts(
+                    &address,
+                    &pool.pool_mint,
+                    &pool.manager_fee_account,
+                    &withdraw_authority
+                )
+                .is_ok());
+            assert!(pool
+                .check_accounts(
+                    &address,
+                    &Pubkey::new_unique(),
+                    &pool.manager_fee_account,
+                    &withdraw_authority
+                )
+                .is_err());
+            assert!(pool
+                .check_accounts(
+                    &address,
+                    &pool.pool_mint,
+                    &pool.manager_fee_account,
+                    &Pubkey::new_unique()
+                )
+                .is_err());
+            assert!(pool.check_reserve_stake(&pool.reserve_stake).is_ok());
+            assert!(pool.check_reserve_stake(&Pubkey::new_unique()).is_err());
+        }
+    }
+
     mod integration_tests {
         use super::*;
 
@@ -1,9 +1,10 @@
 use crate::marinade::program::MarinadeFinance;
-use crate::utils::seeds::ORDER_UNSTAKE_TICKET_ACCOUNT;
+use crate::utils::seeds::{MSOL_ACCOUNT, ORDER_UNSTAKE_TICKET_ACCOUNT};
 use crate::{State, TriggerPoolRebalance};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke_signed;
 use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::system_instruction::transfer;
 
 pub const MARINADE_TICKET_ACCOUNT_SPACE: u64 = 32 + 32 + 8 + 8 + 8;
 
@@ -83,3 +84,50 @@ pub fn create_order_unstake_ticket_account(
 
     Ok(())
 }
+
+// Grow an account owned by this program to `space` bytes, topping up its rent from the payer.
+// Accounts already at least `space` bytes are left as they are.
+pub fn grow_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        let cpi_ctx = CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, rent_shortfall)?;
+    }
+    account.resize(space)?;
+
+    Ok(())
+}
+
+// Transfer SOL held by the msol authority PDA (e.g. released from a claimed Marinade ticket)
+// to a recipient.
+pub fn transfer_from_msol_authority<'a>(
+    state: &Account<'a, State>,
+    msol_authority: &AccountInfo<'a>,
+    recipient: &AccountInfo<'a>,
+    lamports: u64,
+) -> Result<()> {
+    let ix = transfer(msol_authority.key, recipient.key, lamports);
+    let bump = &[state.msol_authority_bump][..];
+    let state_address = state.key();
+    let seeds = &[state_address.as_ref(), MSOL_ACCOUNT, bump][..];
+    invoke_signed(&ix, &[msol_authority.clone(), recipient.clone()], &[seeds])?;
+
+    Ok(())
+}